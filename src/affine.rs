@@ -0,0 +1,93 @@
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 2D affine transformation `[a, b, c, d, e, f]`, which maps the point `[x, y]` to
+/// `[a * x + c * y + e, b * x + d * y + f]`. Angles are in radians and rotate clockwise (the
+/// y-axis points down).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct Affine(pub [f32; 6]);
+
+impl Default for Affine {
+    fn default() -> Self {
+        Self::identity()
+    }
+}
+
+impl Affine {
+    pub fn identity() -> Self {
+        Affine([1.0, 0.0, 0.0, 1.0, 0.0, 0.0])
+    }
+    pub fn translate(tx: f32, ty: f32) -> Self {
+        Affine([1.0, 0.0, 0.0, 1.0, tx, ty])
+    }
+    pub fn scale(sx: f32, sy: f32) -> Self {
+        Affine([sx, 0.0, 0.0, sy, 0.0, 0.0])
+    }
+    pub fn rotate(angle: f32) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        Affine([cos, sin, -sin, cos, 0.0, 0.0])
+    }
+    /// Rotation around the specified point.
+    pub fn rotate_around(angle: f32, center: [f32; 2]) -> Self {
+        Self::translate(-center[0], -center[1])
+            .then(Self::rotate(angle))
+            .then(Self::translate(center[0], center[1]))
+    }
+    /// Skews the x-axis by `angle_y` and the y-axis by `angle_x`, like the SVG/CSS `skew`.
+    pub fn skew(angle_x: f32, angle_y: f32) -> Self {
+        Affine([1.0, angle_y.tan(), angle_x.tan(), 1.0, 0.0, 0.0])
+    }
+
+    /// Transformation that first applies `self` and then `other`.
+    pub fn then(&self, other: Affine) -> Affine {
+        let [a0, b0, c0, d0, e0, f0] = self.0;
+        let [a1, b1, c1, d1, e1, f1] = other.0;
+        Affine([
+            a1 * a0 + c1 * b0,
+            b1 * a0 + d1 * b0,
+            a1 * c0 + c1 * d0,
+            b1 * c0 + d1 * d0,
+            a1 * e0 + c1 * f0 + e1,
+            b1 * e0 + d1 * f0 + f1,
+        ])
+    }
+    pub fn then_translate(&self, tx: f32, ty: f32) -> Affine {
+        self.then(Self::translate(tx, ty))
+    }
+    pub fn then_scale(&self, sx: f32, sy: f32) -> Affine {
+        self.then(Self::scale(sx, sy))
+    }
+    pub fn then_rotate(&self, angle: f32) -> Affine {
+        self.then(Self::rotate(angle))
+    }
+
+    /// Returns the inverse transformation, or None if the transformation is not invertible.
+    pub fn invert(&self) -> Option<Affine> {
+        let [a, b, c, d, e, f] = self.0;
+        let det = a * d - b * c;
+        if det == 0.0 || !det.is_finite() {
+            return None;
+        }
+        let inv = 1.0 / det;
+        Some(Affine([
+            d * inv,
+            -b * inv,
+            -c * inv,
+            a * inv,
+            (c * f - d * e) * inv,
+            (b * e - a * f) * inv,
+        ]))
+    }
+
+    pub fn transform_point(&self, p: [f32; 2]) -> [f32; 2] {
+        let [a, b, c, d, e, f] = self.0;
+        [a * p[0] + c * p[1] + e, b * p[0] + d * p[1] + f]
+    }
+
+    /// Transforms a direction, the translation is ignored.
+    pub fn transform_vector(&self, v: [f32; 2]) -> [f32; 2] {
+        let [a, b, c, d, _, _] = self.0;
+        [a * v[0] + c * v[1], b * v[0] + d * v[1]]
+    }
+}
@@ -1,8 +1,12 @@
+use crate::premul::mul_255;
 use crate::rgba::Rgba;
+use crate::srgb_lut::{linear_to_rgb, rgb_to_linear};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
 
 // ===================== Blending Mode Traits =====================
 
-// Default implementations: ColorBlendOverwrite, ColorBlendOpaque, ColorBlendTransparent
+// Default implementations: ColorBlendOverwrite, ColorBlendOpaque, ColorBlendTransparent, ColorBlendTransparentF32, ColorBlendLinear
 pub trait ColorBlendMode: Copy {
     type ColorContext;
 
@@ -10,7 +14,7 @@ pub trait ColorBlendMode: Copy {
     fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext);
 }
 
-// Default implementations: SolidColorAlphaBlendOverwrite, ColorAlphaBlendOverwrite, ColorAlphaBlendOpaque, ColorAlphaBlendTransparent
+// Default implementations: SolidColorAlphaBlendOverwrite, ColorAlphaBlendOverwrite, ColorAlphaBlendOpaque, ColorAlphaBlendTransparent, ColorAlphaBlendTransparentF32, ColorAlphaBlendLinear
 pub trait ColorAlphaBlendMode: Copy {
     type ColorContext;
 
@@ -21,9 +25,16 @@ pub trait ColorAlphaBlendMode: Copy {
     }
 }
 
-// Default implementations: ImageBlendOverwrite, ImageBlendOpaque, ImageBlendTransparent
+// Default implementations: ImageBlendOverwrite, ImageBlendOpaque, ImageBlendTransparent, ImageBlendTransparentF32, ImageBlendLinear
 pub trait ImageBlendMode: Copy {
     fn blend_color(&self, bg: &mut Rgba, color: Rgba);
+
+    /// Blends a row of image pixels, both slices have the same length.
+    fn blend_row(&self, bg: &mut [Rgba], colors: &[Rgba]) {
+        for (bg, color) in bg.iter_mut().zip(colors.iter()) {
+            self.blend_color(bg, *color);
+        }
+    }
 }
 
 // ===================== Helper Functions =====================
@@ -51,6 +62,63 @@ fn alpha_blend(base: &mut Rgba, color: Rgba) {
     *base = Rgba::from_f32([r, g, b, a]);
 }
 
+/// Same as `alpha_blend` (within rounding), but uses integer math. The straight colors are
+/// weighted by their alpha values (keeping the full precision, unlike converting to 8 bit
+/// `PremulRgba` first) and divided by the resulting alpha using one fixed-point reciprocal per
+/// pixel.
+fn premul_alpha_blend(base: &mut Rgba, color: Rgba) {
+    let alpha = color.alpha();
+    if alpha == 0 {
+        return;
+    }
+    if alpha == 255 || base.alpha() == 0 {
+        *base = color;
+    } else if base.alpha() == 255 {
+        // Note: The result is opaque, so the straight color equals the premultiplied color
+        let (a, inv) = (alpha as u32, 255 - (alpha as u32));
+        for i in 0..3 {
+            base.0[i] = (mul_255(color.0[i] as u32, a) + mul_255(base.0[i] as u32, inv)) as u8;
+        }
+    } else {
+        // Note: All values are scaled by 255 * 255, the premultiplied colors are divided by the
+        // resulting alpha using a single fixed-point reciprocal
+        let src_a = (alpha as u32) * 255;
+        let dst_a = (base.alpha() as u32) * (255 - (alpha as u32));
+        let out_a = src_a + dst_a;
+        let recip = (1u64 << 32) / (out_a as u64);
+        for i in 0..3 {
+            let p = (color.0[i] as u32) * src_a + (base.0[i] as u32) * dst_a;
+            base.0[i] = (((p as u64) * recip + (1 << 31)) >> 32).min(255) as u8;
+        }
+        base.0[3] = ((out_a + 127) / 255) as u8;
+    }
+}
+
+/// Source-over blending in linear light, `linear` holds the linear components of the color.
+/// Lookup tables convert between sRGB and linear light.
+fn linear_alpha_blend(base: &mut Rgba, color: Rgba, linear: [u16; 3]) {
+    let alpha = color.alpha();
+    if alpha == 0 {
+        return;
+    }
+    if alpha == 255 || base.alpha() == 0 {
+        *base = color;
+        return;
+    }
+    let bg = rgb_to_linear(base.rgb());
+    let src_a = (alpha as u64) * 255;
+    let dst_a = (base.alpha() as u64) * (255 - (alpha as u64));
+    let out_a = src_a + dst_a;
+    let recip = (1u64 << 32) / out_a;
+    let mut mixed = [0u32; 3];
+    for (i, v) in mixed.iter_mut().enumerate() {
+        let p = (linear[i] as u64) * src_a + (bg[i] as u64) * dst_a;
+        *v = ((p * recip + (1 << 31)) >> 32) as u32;
+    }
+    let [r, g, b] = linear_to_rgb(mixed);
+    *base = Rgba([r, g, b, ((out_a + 127) / 255) as u8]);
+}
+
 fn fast_alpha_blend_opaque(base: &mut Rgba, color: Rgba) {
     let [r1, g1, b1, a1] = color.rgba();
     let [r1, g1, b1, a1] = [r1 as i32, g1 as i32, b1 as i32, a1 as i32];
@@ -110,11 +178,39 @@ impl ColorBlendMode for ColorBlendTransparent {
         color
     }
     fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext) {
-        // TODO Use premultiplied-alpha
+        premul_alpha_blend(bg, *color_context);
+    }
+}
+
+/// Reference implementation of `ColorBlendTransparent` using floating point math.
+#[derive(Copy, Clone, Debug)]
+pub struct ColorBlendTransparentF32;
+impl ColorBlendMode for ColorBlendTransparentF32 {
+    type ColorContext = Rgba;
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        color
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext) {
         alpha_blend(bg, *color_context);
     }
 }
 
+/// Same as `ColorBlendTransparent`, but blends in linear light (gamma-correct), which matches
+/// browsers and design tools for anti-aliased edges and text.
+#[derive(Copy, Clone, Debug)]
+pub struct ColorBlendLinear;
+impl ColorBlendMode for ColorBlendLinear {
+    type ColorContext = (Rgba, [u16; 3]);
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        (color, rgb_to_linear(color.rgb()))
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext) {
+        linear_alpha_blend(bg, color_context.0, color_context.1);
+    }
+}
+
 // ===================== ColorAlphaBlendMode Implementations =====================
 
 #[derive(Copy, Clone, Debug)]
@@ -173,7 +269,23 @@ impl ColorAlphaBlendMode for ColorAlphaBlendTransparent {
         color
     }
     fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext, alpha: u8) {
-        // TODO Use premultiplied-alpha
+        let mut color = *color_context;
+        let value = (color.alpha() as u32) * (alpha as u32) / 255;
+        color.set_alpha(value as u8);
+        premul_alpha_blend(bg, color);
+    }
+}
+
+/// Reference implementation of `ColorAlphaBlendTransparent` using floating point math.
+#[derive(Copy, Clone, Debug)]
+pub struct ColorAlphaBlendTransparentF32;
+impl ColorAlphaBlendMode for ColorAlphaBlendTransparentF32 {
+    type ColorContext = Rgba;
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        color
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext, alpha: u8) {
         let mut color = *color_context;
         let value = (color.alpha() as u32) * (alpha as u32) / 255;
         color.set_alpha(value as u8);
@@ -181,6 +293,23 @@ impl ColorAlphaBlendMode for ColorAlphaBlendTransparent {
     }
 }
 
+/// Same as `ColorAlphaBlendTransparent`, but blends in linear light (gamma-correct).
+#[derive(Copy, Clone, Debug)]
+pub struct ColorAlphaBlendLinear;
+impl ColorAlphaBlendMode for ColorAlphaBlendLinear {
+    type ColorContext = (Rgba, [u16; 3]);
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        (color, rgb_to_linear(color.rgb()))
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext, alpha: u8) {
+        let (mut color, linear) = *color_context;
+        let value = (color.alpha() as u32) * (alpha as u32) / 255;
+        color.set_alpha(value as u8);
+        linear_alpha_blend(bg, color, linear);
+    }
+}
+
 // ===================== ImageBlendMode Implementations =====================
 
 #[derive(Copy, Clone, Debug)]
@@ -189,6 +318,9 @@ impl ImageBlendMode for ImageBlendOverwrite {
     fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
         *bg = color;
     }
+    fn blend_row(&self, bg: &mut [Rgba], colors: &[Rgba]) {
+        bg.copy_from_slice(colors);
+    }
 }
 
 #[derive(Copy, Clone, Debug)]
@@ -202,7 +334,386 @@ impl ImageBlendMode for ImageBlendOpaque {
 #[derive(Copy, Clone, Debug)]
 pub struct ImageBlendTransparent;
 impl ImageBlendMode for ImageBlendTransparent {
+    fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
+        premul_alpha_blend(bg, color);
+    }
+}
+
+/// Reference implementation of `ImageBlendTransparent` using floating point math.
+#[derive(Copy, Clone, Debug)]
+pub struct ImageBlendTransparentF32;
+impl ImageBlendMode for ImageBlendTransparentF32 {
     fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
         alpha_blend(bg, color);
     }
 }
+
+/// Same as `ImageBlendTransparent`, but blends in linear light (gamma-correct).
+#[derive(Copy, Clone, Debug)]
+pub struct ImageBlendLinear;
+impl ImageBlendMode for ImageBlendLinear {
+    fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
+        linear_alpha_blend(bg, color, rgb_to_linear(color.rgb()));
+    }
+}
+
+// ===================== ImageBlendMode Adapters =====================
+
+/// Multiplies every image pixel by the tint color and the opacity before blending it using the
+/// wrapped mode. A white tint with an opacity of 1.0 leaves the image unchanged.
+#[derive(Copy, Clone, Debug)]
+pub struct ImageBlendTinted<Mode: ImageBlendMode> {
+    mode: Mode,
+    tint: Rgba,
+    opacity: f32,
+    factor: [u32; 4],
+}
+impl<Mode: ImageBlendMode> ImageBlendTinted<Mode> {
+    pub fn new(mode: Mode, tint: Rgba) -> Self {
+        Self {
+            mode,
+            tint,
+            opacity: 1.0,
+            factor: Self::factor(tint, 1.0),
+        }
+    }
+
+    /// Opacity between 0.0 (invisible) and 1.0, multiplied with the alpha of the tint.
+    pub fn with_opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self.factor = Self::factor(self.tint, opacity);
+        self
+    }
+
+    pub fn tint(&self) -> Rgba {
+        self.tint
+    }
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    fn factor(tint: Rgba, opacity: f32) -> [u32; 4] {
+        let [r, g, b, a] = tint.rgba();
+        let a = ((a as f32) * opacity.clamp(0.0, 1.0)).round() as u32;
+        [r as u32, g as u32, b as u32, a]
+    }
+}
+impl<Mode: ImageBlendMode> ImageBlendMode for ImageBlendTinted<Mode> {
+    fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
+        let [r, g, b, a] = color.rgba();
+        let f = &self.factor;
+        let color = Rgba([
+            ((r as u32) * f[0] / 255) as u8,
+            ((g as u32) * f[1] / 255) as u8,
+            ((b as u32) * f[2] / 255) as u8,
+            ((a as u32) * f[3] / 255) as u8,
+        ]);
+        self.mode.blend_color(bg, color);
+    }
+}
+
+// ===================== Porter-Duff Compositing =====================
+
+/// Porter-Duff compositing operators, usable as image, color and color alpha blend mode. The
+/// source is the color that is drawn, the destination is the existing color. Partially covered
+/// pixels are interpolated between the destination and the composited color. Note: Only the
+/// pixels that are drawn are affected, for example `SrcIn` does not clear the destination
+/// outside of a filled shape.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum PorterDuff {
+    /// Source only (replaces the destination).
+    Src,
+    /// Destination only (leaves the destination unchanged).
+    Dst,
+    /// Source over the destination (regular alpha blending).
+    SrcOver,
+    /// Destination over the source.
+    DstOver,
+    /// Source where the destination is opaque.
+    SrcIn,
+    /// Destination where the source is opaque.
+    DstIn,
+    /// Source where the destination is transparent.
+    SrcOut,
+    /// Destination where the source is transparent (punches holes).
+    DstOut,
+    /// Source where the destination is opaque, the destination elsewhere.
+    SrcAtop,
+    /// Destination where the source is opaque, the source elsewhere.
+    DstAtop,
+    /// Source and destination where the other one is transparent.
+    Xor,
+    /// Sum of source and destination.
+    Plus,
+}
+
+impl PorterDuff {
+    /// Fractions of the source and destination that contribute to the result.
+    fn factors(self, src_alpha: f32, dst_alpha: f32) -> (f32, f32) {
+        match self {
+            PorterDuff::Src => (1.0, 0.0),
+            PorterDuff::Dst => (0.0, 1.0),
+            PorterDuff::SrcOver => (1.0, 1.0 - src_alpha),
+            PorterDuff::DstOver => (1.0 - dst_alpha, 1.0),
+            PorterDuff::SrcIn => (dst_alpha, 0.0),
+            PorterDuff::DstIn => (0.0, src_alpha),
+            PorterDuff::SrcOut => (1.0 - dst_alpha, 0.0),
+            PorterDuff::DstOut => (0.0, 1.0 - src_alpha),
+            PorterDuff::SrcAtop => (dst_alpha, 1.0 - src_alpha),
+            PorterDuff::DstAtop => (1.0 - dst_alpha, src_alpha),
+            PorterDuff::Xor => (1.0 - dst_alpha, 1.0 - src_alpha),
+            PorterDuff::Plus => (1.0, 1.0),
+        }
+    }
+
+    /// Composites the color with the destination, the coverage ranges from 0.0 to 1.0.
+    fn composite(self, bg: &mut Rgba, color: Rgba, coverage: f32) {
+        if self == PorterDuff::Dst || coverage <= 0.0 {
+            return;
+        }
+        if self == PorterDuff::Src && coverage >= 1.0 {
+            *bg = color;
+            return;
+        }
+        let src = premultiply(color.rgba_f32());
+        let dst = premultiply(bg.rgba_f32());
+        let (fa, fb) = self.factors(src[3], dst[3]);
+        let mut result = [0.0; 4];
+        for i in 0..4 {
+            let v = (fa * src[i] + fb * dst[i]).min(1.0);
+            result[i] = dst[i] + (v - dst[i]) * coverage;
+        }
+        *bg = unpremultiply(result);
+    }
+}
+
+fn premultiply(c: [f32; 4]) -> [f32; 4] {
+    [c[0] * c[3], c[1] * c[3], c[2] * c[3], c[3]]
+}
+
+fn unpremultiply(c: [f32; 4]) -> Rgba {
+    if c[3] <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    let inv = 1.0 / c[3];
+    Rgba::from_f32([c[0] * inv, c[1] * inv, c[2] * inv, c[3]])
+}
+
+impl ImageBlendMode for PorterDuff {
+    fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
+        self.composite(bg, color, 1.0);
+    }
+}
+
+impl ColorBlendMode for PorterDuff {
+    type ColorContext = Rgba;
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        color
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext) {
+        self.composite(bg, *color_context, 1.0);
+    }
+}
+
+impl ColorAlphaBlendMode for PorterDuff {
+    type ColorContext = Rgba;
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        color
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext, alpha: u8) {
+        self.composite(bg, *color_context, (alpha as f32) * (1.0 / 255.0));
+    }
+}
+
+// ===================== Separable and Non-Separable Blend Modes =====================
+
+/// Blend modes of the W3C Compositing and Blending specification (like CSS `mix-blend-mode`),
+/// usable as image, color and color alpha blend mode. The blended color is composited over the
+/// destination (source-over). Note: The non-separable modes (`Hue`, `Saturation`, `Color` and
+/// `Luminosity`) use the luminosity of the specification instead of the HSL lightness of `Hsl`,
+/// which keeps the results identical to browsers and image editors.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum MixBlendMode {
+    Normal,
+    Multiply,
+    Screen,
+    Overlay,
+    Darken,
+    Lighten,
+    ColorDodge,
+    ColorBurn,
+    HardLight,
+    SoftLight,
+    Difference,
+    Exclusion,
+    Hue,
+    Saturation,
+    Color,
+    Luminosity,
+}
+
+impl MixBlendMode {
+    /// Mixes the (non-premultiplied) backdrop and source colors.
+    fn mix(self, cb: [f32; 3], cs: [f32; 3]) -> [f32; 3] {
+        let separable =
+            |f: fn(f32, f32) -> f32| [f(cb[0], cs[0]), f(cb[1], cs[1]), f(cb[2], cs[2])];
+        match self {
+            MixBlendMode::Normal => cs,
+            MixBlendMode::Multiply => separable(|b, s| b * s),
+            MixBlendMode::Screen => separable(screen),
+            MixBlendMode::Overlay => separable(|b, s| hard_light(s, b)),
+            MixBlendMode::Darken => separable(f32::min),
+            MixBlendMode::Lighten => separable(f32::max),
+            MixBlendMode::ColorDodge => separable(|b, s| {
+                if b <= 0.0 {
+                    0.0
+                } else if s >= 1.0 {
+                    1.0
+                } else {
+                    (b / (1.0 - s)).min(1.0)
+                }
+            }),
+            MixBlendMode::ColorBurn => separable(|b, s| {
+                if b >= 1.0 {
+                    1.0
+                } else if s <= 0.0 {
+                    0.0
+                } else {
+                    1.0 - ((1.0 - b) / s).min(1.0)
+                }
+            }),
+            MixBlendMode::HardLight => separable(hard_light),
+            MixBlendMode::SoftLight => separable(|b, s| {
+                if s <= 0.5 {
+                    b - (1.0 - 2.0 * s) * b * (1.0 - b)
+                } else {
+                    let d = if b <= 0.25 {
+                        ((16.0 * b - 12.0) * b + 4.0) * b
+                    } else {
+                        b.sqrt()
+                    };
+                    b + (2.0 * s - 1.0) * (d - b)
+                }
+            }),
+            MixBlendMode::Difference => separable(|b, s| (b - s).abs()),
+            MixBlendMode::Exclusion => separable(|b, s| b + s - 2.0 * b * s),
+            MixBlendMode::Hue => set_lum(set_sat(cs, sat(cb)), lum(cb)),
+            MixBlendMode::Saturation => set_lum(set_sat(cb, sat(cs)), lum(cb)),
+            MixBlendMode::Color => set_lum(cs, lum(cb)),
+            MixBlendMode::Luminosity => set_lum(cb, lum(cs)),
+        }
+    }
+
+    /// Blends the color with the destination, the coverage ranges from 0.0 to 1.0.
+    fn composite(self, bg: &mut Rgba, color: Rgba, coverage: f32) {
+        if coverage <= 0.0 || color.alpha() == 0 {
+            return;
+        }
+        let [rb, gb, bb, ab] = bg.rgba_f32();
+        let [rs, gs, bs, a_s] = color.rgba_f32();
+        let mixed = self.mix([rb, gb, bb], [rs, gs, bs]);
+        let cs = [rs, gs, bs];
+        let dst = premultiply([rb, gb, bb, ab]);
+        let mut result = [0.0; 4];
+        for i in 0..3 {
+            // Note: The mixed color only applies where the destination is opaque
+            let c = (1.0 - ab) * cs[i] + ab * mixed[i];
+            result[i] = a_s * c + (1.0 - a_s) * dst[i];
+        }
+        result[3] = a_s + (1.0 - a_s) * ab;
+        for i in 0..4 {
+            result[i] = dst[i] + (result[i] - dst[i]) * coverage;
+        }
+        *bg = unpremultiply(result);
+    }
+}
+
+fn screen(b: f32, s: f32) -> f32 {
+    b + s - b * s
+}
+
+fn hard_light(b: f32, s: f32) -> f32 {
+    if s <= 0.5 {
+        b * 2.0 * s
+    } else {
+        screen(b, 2.0 * s - 1.0)
+    }
+}
+
+fn lum(c: [f32; 3]) -> f32 {
+    0.3 * c[0] + 0.59 * c[1] + 0.11 * c[2]
+}
+
+fn set_lum(c: [f32; 3], l: f32) -> [f32; 3] {
+    let d = l - lum(c);
+    clip_color([c[0] + d, c[1] + d, c[2] + d])
+}
+
+fn clip_color(c: [f32; 3]) -> [f32; 3] {
+    let l = lum(c);
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+    let mut c = c;
+    if min < 0.0 {
+        c = [
+            l + (c[0] - l) * l / (l - min),
+            l + (c[1] - l) * l / (l - min),
+            l + (c[2] - l) * l / (l - min),
+        ];
+    }
+    if max > 1.0 {
+        c = [
+            l + (c[0] - l) * (1.0 - l) / (max - l),
+            l + (c[1] - l) * (1.0 - l) / (max - l),
+            l + (c[2] - l) * (1.0 - l) / (max - l),
+        ];
+    }
+    c
+}
+
+fn sat(c: [f32; 3]) -> f32 {
+    c[0].max(c[1]).max(c[2]) - c[0].min(c[1]).min(c[2])
+}
+
+fn set_sat(c: [f32; 3], s: f32) -> [f32; 3] {
+    let min = c[0].min(c[1]).min(c[2]);
+    let max = c[0].max(c[1]).max(c[2]);
+    if max > min {
+        let f = s / (max - min);
+        [(c[0] - min) * f, (c[1] - min) * f, (c[2] - min) * f]
+    } else {
+        [0.0, 0.0, 0.0]
+    }
+}
+
+impl ImageBlendMode for MixBlendMode {
+    fn blend_color(&self, bg: &mut Rgba, color: Rgba) {
+        self.composite(bg, color, 1.0);
+    }
+}
+
+impl ColorBlendMode for MixBlendMode {
+    type ColorContext = Rgba;
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        color
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext) {
+        self.composite(bg, *color_context, 1.0);
+    }
+}
+
+impl ColorAlphaBlendMode for MixBlendMode {
+    type ColorContext = Rgba;
+
+    fn prepare_color(&self, color: Rgba) -> Self::ColorContext {
+        color
+    }
+    fn blend_color(&self, bg: &mut Rgba, color_context: &Self::ColorContext, alpha: u8) {
+        self.composite(bg, *color_context, (alpha as f32) * (1.0 / 255.0));
+    }
+}
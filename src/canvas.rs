@@ -1,30 +1,84 @@
+use crate::affine::Affine;
 use crate::blend::{
-    ColorBlendMode, ColorBlendOverwrite, ColorBlendTransparent, ImageBlendMode,
-    ImageBlendTransparent,
+    ColorAlphaBlendMode, ColorAlphaBlendTransparent, ColorBlendMode, ColorBlendOverwrite,
+    ColorBlendTransparent, ImageBlendMode, ImageBlendTinted, ImageBlendTransparent,
 };
+use crate::damage::DamageTracker;
 use crate::font::Font;
 use crate::img::Image;
+use crate::line::{plot_aliased_line, plot_antialiased_line, LineMode};
+use crate::mask::Mask;
+use crate::paint::Paint;
+use crate::path::Path;
+use crate::pixels::Pixels;
+use crate::premul::{lerp_premul, mul_255};
+use crate::raster::{bounding_rect, FillRule, Rasterizer};
 use crate::rect::Rect;
 use crate::rgba::Rgba;
 use crate::rows::{RowsIter, RowsMutIter};
+use crate::sample::{sample, SampleEdge, SampleFilter};
+use crate::stroke::{stroke_outline, StrokeStyle};
 use std::cmp::{max, min};
+use std::sync::Arc;
 
 pub struct Canvas<'a> {
-    img: &'a mut Image,
+    pixels: Pixels<'a>,
     pos: [i32; 2],
     dim: [u32; 2],
     idx0: usize,
     stride: usize,
+    // Position of the top-left pixel of the image, which is not [0, 0] for offscreen layers
+    origin: [i32; 2],
+    // Region of the image that this canvas may access, see `split_at_y`
+    bounds: Rect,
+    // Effective clip mask (the intersection of every pushed mask) and the masks below it
+    clip: Option<Arc<Mask>>,
+    clip_stack: Vec<Option<Arc<Mask>>>,
+    // Maps the coordinates of the drawing operations to the coordinate space of the image
+    transform: Affine,
+    damage: Option<DamageTracker>,
 }
 impl<'a> Canvas<'a> {
     pub(crate) fn new(img: &'a mut Image, idx0: usize, pos: [i32; 2], dim: [u32; 2]) -> Canvas<'a> {
         let stride = img.stride();
+        let origin = if stride > 0 {
+            [
+                pos[0] - ((idx0 % stride) as i32),
+                pos[1] - ((idx0 / stride) as i32),
+            ]
+        } else {
+            pos
+        };
+        let bounds = Rect::new(origin, img.dim());
         Canvas {
-            img,
+            pixels: Pixels::new(img),
             pos,
             dim,
             idx0,
             stride,
+            origin,
+            bounds,
+            clip: None,
+            clip_stack: Vec::new(),
+            transform: Affine::identity(),
+            damage: None,
+        }
+    }
+
+    /// Canvas for the same region that borrows this canvas.
+    pub(crate) fn reborrow(&mut self) -> Canvas<'_> {
+        Canvas {
+            pixels: self.pixels.reborrow(),
+            pos: self.pos,
+            dim: self.dim,
+            idx0: self.idx0,
+            stride: self.stride,
+            origin: self.origin,
+            bounds: self.bounds,
+            clip: self.clip.clone(),
+            clip_stack: Vec::new(),
+            transform: self.transform,
+            damage: self.damage.clone(),
         }
     }
 
@@ -41,14 +95,32 @@ impl<'a> Canvas<'a> {
     pub fn height(&self) -> u32 {
         self.dim[1]
     }
+    pub fn rect(&self) -> Rect {
+        Rect::new(self.pos, self.dim)
+    }
+
+    /// Region of the image that this canvas may access. This is the whole image, unless the
+    /// canvas was split.
+    pub(crate) fn bounds(&self) -> Rect {
+        self.bounds
+    }
+
+    /// Color of the pixel within `bounds()`, which may be outside of the canvas.
+    pub(crate) fn pixel_at(&self, pos: [i32; 2]) -> Rgba {
+        assert!(
+            self.bounds.contains(pos),
+            "Position is outside of the bounds"
+        );
+        let [x, y] = [pos[0] - self.origin[0], pos[1] - self.origin[1]];
+        self.pixels.get((x as usize) + (y as usize) * self.stride)
+    }
 
     pub fn nth_row(&self, n: u32) -> &[Rgba] {
         if n >= self.dim[1] {
             panic!("Row does not exist");
         } else {
             let idx = self.idx0 + self.stride * (n as usize);
-            let end = idx + (self.dim[0] as usize);
-            &self.img.buffer()[idx..end]
+            self.pixels.slice(idx, self.dim[0] as usize)
         }
     }
 
@@ -57,8 +129,7 @@ impl<'a> Canvas<'a> {
             panic!("Row does not exist");
         } else {
             let idx = self.idx0 + self.stride * (n as usize);
-            let end = idx + (self.dim[0] as usize);
-            &mut self.img.buffer_mut()[idx..end]
+            self.pixels.slice_mut(idx, self.dim[0] as usize)
         }
     }
 
@@ -68,9 +139,7 @@ impl<'a> Canvas<'a> {
         let width = self.dim[0] as usize;
         let stride = self.stride;
         let max_idx = idx0 + (self.dim[1] as usize) * stride;
-        unsafe {
-            RowsIter::unchecked_from_index(self.img.buffer(), idx0, pos0, width, stride, max_idx)
-        }
+        unsafe { RowsIter::from_pixels(&self.pixels, idx0, pos0, width, stride, max_idx) }
     }
 
     pub fn rows_iter_mut<'b>(&'b mut self) -> RowsMutIter<'b> {
@@ -79,16 +148,7 @@ impl<'a> Canvas<'a> {
         let width = self.dim[0] as usize;
         let stride = self.stride;
         let max_idx = idx0 + (self.dim[1] as usize) * stride;
-        unsafe {
-            RowsMutIter::unchecked_from_index(
-                self.img.buffer_mut(),
-                idx0,
-                pos0,
-                width,
-                stride,
-                max_idx,
-            )
-        }
+        unsafe { RowsMutIter::from_pixels(&mut self.pixels, idx0, pos0, width, stride, max_idx) }
     }
 
     /// Returns a sub-section of this canvas that overlaps with the specified rectangle. The sub-canvas retains
@@ -118,19 +178,89 @@ impl<'a> Canvas<'a> {
             let width = (eff_end_x - eff_start_x) as u32;
             let height = (eff_end_y - eff_start_y) as u32;
 
-            let stride = self.stride;
+            let rect = Rect::new([eff_start_x, eff_start_y], [width, height]);
+            // Safety: The sub-canvas borrows this canvas
+            Some(unsafe { self.alias(rect) })
+        }
+    }
+
+    /// Canvas for the rectangle, which must be within this canvas.
+    ///
+    /// Safety: The lifetime must not exceed the lifetime of this canvas, and canvases that are
+    /// used at the same time must have disjoint rectangles.
+    unsafe fn alias<'b>(&self, rect: Rect) -> Canvas<'b> {
+        // Note: This will be valid since we know the current canvas only contained valid indexes
+        let [x, y] = [rect.x() - self.origin[0], rect.y() - self.origin[1]];
+        let idx0 = (x as usize) + (y as usize) * self.stride;
+        Canvas {
+            pixels: self.pixels.alias(),
+            pos: rect.pos,
+            dim: rect.dim,
+            idx0,
+            stride: self.stride,
+            origin: self.origin,
+            bounds: self.bounds,
+            // Note: Masks use the coordinate space of the image, so the mask can be shared
+            clip: self.clip.clone(),
+            clip_stack: Vec::new(),
+            transform: self.transform,
+            damage: self.damage.clone(),
+        }
+    }
 
-            // Note: This will be valid since we know the current canvas only contained valid indexes
-            let idx0 = self.img.index_at([eff_start_x, eff_start_y]);
+    /// Splits the canvas into the parts above and below `y`, which can be used at the same time
+    /// (for example by different threads). A part is None if it would be empty. Like
+    /// `sub_canvas`, the parts use the coordinate space of the image, and they can only access
+    /// their own pixels (for example `flood_fill` never leaves the part).
+    pub fn split_at_y(&mut self, y: i32) -> (Option<Canvas<'_>>, Option<Canvas<'_>>) {
+        let y = y.clamp(self.pos[1], self.pos[1] + self.dim[1] as i32);
+        let top = Rect::new(self.pos, [self.dim[0], (y - self.pos[1]) as u32]);
+        let bottom = Rect::new([self.pos[0], y], [self.dim[0], self.dim[1] - top.height()]);
+        let mut parts = self.split_rects(&[top, bottom]).into_iter();
+        (parts.next().unwrap(), parts.next().unwrap())
+    }
 
-            Some(Canvas {
-                img: self.img,
-                pos: [eff_start_x, eff_start_y],
-                dim: [width, height],
-                idx0: idx0,
-                stride: stride,
+    /// Splits the canvas into the parts left and right of `x`, see `split_at_y`.
+    pub fn split_at_x(&mut self, x: i32) -> (Option<Canvas<'_>>, Option<Canvas<'_>>) {
+        let x = x.clamp(self.pos[0], self.pos[0] + self.dim[0] as i32);
+        let left = Rect::new(self.pos, [(x - self.pos[0]) as u32, self.dim[1]]);
+        let right = Rect::new([x, self.pos[1]], [self.dim[0] - left.width(), self.dim[1]]);
+        let mut parts = self.split_rects(&[left, right]).into_iter();
+        (parts.next().unwrap(), parts.next().unwrap())
+    }
+
+    /// Splits the canvas into (at most) `n` bands of rows with about the same height, see
+    /// `split_at_y`. Empty bands are left out.
+    pub fn split_rows(&mut self, n: usize) -> Vec<Canvas<'_>> {
+        let n = n.max(1) as u64;
+        let height = self.dim[1] as u64;
+        let rects: Vec<Rect> = (0..n)
+            .map(|i| {
+                let y0 = (height * i / n) as u32;
+                let y1 = (height * (i + 1) / n) as u32;
+                Rect::new(
+                    [self.pos[0], self.pos[1] + y0 as i32],
+                    [self.dim[0], y1 - y0],
+                )
             })
-        }
+            .collect();
+        self.split_rects(&rects).into_iter().flatten().collect()
+    }
+
+    /// Canvases for disjoint rectangles within this canvas, None for empty rectangles.
+    fn split_rects(&mut self, rects: &[Rect]) -> Vec<Option<Canvas<'_>>> {
+        rects
+            .iter()
+            .map(|rect| {
+                if rect.is_empty() {
+                    return None;
+                }
+                // Safety: The rectangles are disjoint and the parts borrow this canvas
+                let mut part = unsafe { self.alias(*rect) };
+                part.bounds = *rect;
+                Some(part)
+            })
+            .collect()
     }
 
     pub fn sub_canvas_rect<'b>(&'b mut self, rect: Rect) -> Option<Canvas<'b>> {
@@ -139,8 +269,8 @@ impl<'a> Canvas<'a> {
 
     // Performs the same operation as sub_canvas, but takes ownership instead
     pub fn into_sub_canvas(mut self, pos: [i32; 2], dim: [u32; 2]) -> Option<Canvas<'a>> {
-        let (pos, dim, idx0) = if let Some(ss) = self.sub_canvas(pos, dim) {
-            (ss.pos, ss.dim, ss.idx0)
+        let (pos, dim, idx0, clip) = if let Some(ss) = self.sub_canvas(pos, dim) {
+            (ss.pos, ss.dim, ss.idx0, ss.clip)
         } else {
             return None;
         };
@@ -148,6 +278,7 @@ impl<'a> Canvas<'a> {
         self.pos = pos;
         self.dim = dim;
         self.idx0 = idx0;
+        self.clip = clip;
         Some(self)
     }
 
@@ -155,6 +286,225 @@ impl<'a> Canvas<'a> {
         self.into_sub_canvas(rect.pos, rect.dim)
     }
 
+    /// Clips every drawing operation to the mask, see `push_clip_mask`.
+    pub fn with_clip_mask(mut self, mask: &Mask) -> Self {
+        self.push_clip_mask(mask);
+        self
+    }
+
+    /// Restricts drawing to the mask (intersected with the current clip mask). The result of
+    /// every drawing operation is scaled by the coverage of the mask, positions outside of the
+    /// mask are not modified. Note: The raw pixel accessors like `nth_row_mut` are not clipped.
+    pub fn push_clip_mask(&mut self, mask: &Mask) {
+        let rect = self.rect();
+        let mut clip = Mask::new(rect);
+        if let Some(overlap) = mask.rect().intersect(rect) {
+            for y in overlap.y()..overlap.end_y() {
+                for x in overlap.x()..overlap.end_x() {
+                    let current = match &self.clip {
+                        Some(current) => current.get([x, y]),
+                        None => 255,
+                    };
+                    let value = mul_255(current as u32, mask.get([x, y]) as u32) as u8;
+                    clip.try_set([x, y], value);
+                }
+            }
+        }
+        let prev = self.clip.replace(Arc::new(clip));
+        self.clip_stack.push(prev);
+    }
+
+    /// Restores the clip mask that was active before the last `push_clip_mask`. Returns false if
+    /// no mask was pushed on this canvas.
+    pub fn pop_clip_mask(&mut self) -> bool {
+        match self.clip_stack.pop() {
+            Some(prev) => {
+                self.clip = prev;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Current clip mask, which is shared with sub-canvases. Its rectangle is the rectangle of
+    /// the canvas that pushed it, which may extend beyond this canvas.
+    pub fn clip_mask(&self) -> Option<&Mask> {
+        self.clip.as_deref()
+    }
+
+    /// Records the rectangles that are changed by the drawing operations of this canvas (and its
+    /// sub-canvases) in the tracker. Note: Changes using the raw pixel accessors like
+    /// `nth_row_mut` are not recorded.
+    pub fn with_damage_tracker(mut self, tracker: &DamageTracker) -> Self {
+        self.damage = Some(tracker.clone());
+        self
+    }
+
+    pub fn damage_tracker(&self) -> Option<&DamageTracker> {
+        self.damage.as_ref()
+    }
+
+    /// Records the rectangle in the damage tracker, limited to the drawable area.
+    pub(crate) fn add_damage(&self, rect: Rect) {
+        if let Some(tracker) = &self.damage {
+            let area = match &self.clip {
+                Some(clip) => clip.rect().intersect(self.rect()),
+                None => Some(self.rect()),
+            };
+            if let Some(rect) = area.and_then(|area| rect.intersect(area)) {
+                tracker.add(rect);
+            }
+        }
+    }
+
+    /// Records the rectangle (local coordinates) once and performs the drawing without recording
+    /// every pixel, for example for the pixels of a glyph.
+    pub(crate) fn with_damage_rect<F: FnOnce(&mut Self)>(&mut self, rect: Rect, f: F) {
+        if self.damage.is_none() {
+            return f(self);
+        }
+        if let Some(rect) = self.map_bounds(rect) {
+            self.add_damage(rect);
+        }
+        let damage = self.damage.take();
+        f(self);
+        self.damage = damage;
+    }
+
+    /// Transformation that maps the positions of the drawing operations to the coordinate space of
+    /// the image, see `set_transform`.
+    pub fn transform(&self) -> Affine {
+        self.transform
+    }
+
+    /// Sets the transformation that every drawing operation uses, which allows drawing with
+    /// local coordinates. Shapes, lines and images are transformed exactly. Rectangles are snapped
+    /// to whole pixels unless the transformation rotates or skews them. Single pixel accessors
+    /// like `try_get_color` and `try_set_color` use the pixel that contains the transformed pixel
+    /// center, and text is resampled unless the transformation only translates by whole pixels.
+    /// Note: The position and size of the canvas, sub-canvas rectangles, clip masks and row
+    /// accessors like `nth_row` always use the coordinate space of the image.
+    pub fn set_transform(&mut self, transform: Affine) {
+        self.transform = transform;
+    }
+
+    pub fn with_transform(mut self, transform: Affine) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    /// Moves the local origin `[0, 0]` to the top-left corner of this canvas, keeping the scale
+    /// of the current transformation. Drawing functions can then use local coordinates with any
+    /// sub-canvas.
+    pub fn with_local_origin(mut self) -> Self {
+        let [a, b, c, d, _, _] = self.transform.0;
+        self.transform = Affine([a, b, c, d, self.pos[0] as f32, self.pos[1] as f32]);
+        self
+    }
+
+    /// Translates the local coordinates, the translation is applied before the current
+    /// transformation.
+    pub fn translate(&mut self, tx: f32, ty: f32) {
+        self.transform = Affine::translate(tx, ty).then(self.transform);
+    }
+
+    /// Scales the local coordinates, the scale is applied before the current transformation.
+    pub fn scale(&mut self, sx: f32, sy: f32) {
+        self.transform = Affine::scale(sx, sy).then(self.transform);
+    }
+
+    /// Returns the offset if the transformation only translates by whole pixels.
+    fn integer_offset(&self) -> Option<[i32; 2]> {
+        let [a, b, c, d, e, f] = self.transform.0;
+        if [a, b, c, d] == [1.0, 0.0, 0.0, 1.0] && e.fract() == 0.0 && f.fract() == 0.0 {
+            Some([e as i32, f as i32])
+        } else {
+            None
+        }
+    }
+
+    /// Position of the pixel that contains the transformed center of the pixel.
+    pub(crate) fn map_pixel(&self, pos: [i32; 2]) -> [i32; 2] {
+        if let Some([dx, dy]) = self.integer_offset() {
+            return [pos[0] + dx, pos[1] + dy];
+        }
+        let [x, y] = self
+            .transform
+            .transform_point([pos[0] as f32 + 0.5, pos[1] as f32 + 0.5]);
+        [x.floor() as i32, y.floor() as i32]
+    }
+
+    /// Transformed rectangle snapped to whole pixels, or None if the transformation rotates or
+    /// skews it.
+    fn map_rect(&self, rect: Rect) -> Option<Rect> {
+        let [_, b, c, _, _, _] = self.transform.0;
+        if b != 0.0 || c != 0.0 {
+            return None;
+        }
+        let p0 = self
+            .transform
+            .transform_point([rect.x() as f32, rect.y() as f32]);
+        let p1 = self
+            .transform
+            .transform_point([rect.end_x() as f32, rect.end_y() as f32]);
+        let [x0, x1] = [p0[0].min(p1[0]).round(), p0[0].max(p1[0]).round()];
+        let [y0, y1] = [p0[1].min(p1[1]).round(), p0[1].max(p1[1]).round()];
+        let [x0, y0] = [x0 as i32, y0 as i32];
+        Some(Rect::new(
+            [x0, y0],
+            [(x1 as i32 - x0) as u32, (y1 as i32 - y0) as u32],
+        ))
+    }
+
+    /// Corners of the transformed rectangle.
+    fn map_corners(&self, rect: Rect) -> [[f32; 2]; 4] {
+        let [x0, y0] = [rect.x() as f32, rect.y() as f32];
+        let [x1, y1] = [rect.end_x() as f32, rect.end_y() as f32];
+        [[x0, y0], [x1, y0], [x1, y1], [x0, y1]].map(|p| self.transform.transform_point(p))
+    }
+
+    /// Bounding rectangle of the transformed rectangle.
+    pub(crate) fn map_bounds(&self, rect: Rect) -> Option<Rect> {
+        match self.integer_offset() {
+            Some([dx, dy]) => Some(Rect::new([rect.x() + dx, rect.y() + dy], rect.dim)),
+            None => bounding_rect(std::iter::once(&self.map_corners(rect)[..])),
+        }
+    }
+
+    /// Paint that uses local coordinates.
+    pub(crate) fn local_paint<'p, P: Paint>(&self, paint: &'p P) -> LocalPaint<'p, P> {
+        let inv = match self.integer_offset() {
+            Some([0, 0]) => None,
+            _ => self.transform.invert(),
+        };
+        LocalPaint { paint, inv }
+    }
+
+    /// Applies the operation to the pixel, the result is scaled by the coverage of the clip mask.
+    /// Returns false if the position is outside of the canvas.
+    #[inline]
+    pub(crate) fn modify_pixel<F: FnOnce(&mut Rgba)>(&mut self, pos: [i32; 2], f: F) -> bool {
+        let idx = match self.try_index_at(pos) {
+            Some(idx) => idx,
+            None => return false,
+        };
+        let coverage = match &self.clip {
+            Some(clip) => clip.get(pos),
+            None => 255,
+        };
+        let pixel = self.pixels.get_mut(idx);
+        match coverage {
+            0 => {}
+            255 => f(pixel),
+            _ => {
+                let old = *pixel;
+                f(pixel);
+                *pixel = lerp_premul(old, *pixel, coverage);
+            }
+        }
+        true
+    }
+
     fn try_index_at(&self, pos: [i32; 2]) -> Option<usize> {
         let [x, y] = pos;
         let (dx, dy) = (x - self.pos[0], y - self.pos[1]);
@@ -166,28 +516,32 @@ impl<'a> Canvas<'a> {
     }
 
     pub fn try_get_color(&self, pos: [i32; 2]) -> Option<Rgba> {
-        if let Some(idx) = self.try_index_at(pos) {
-            Some(self.img.get(idx))
+        if let Some(idx) = self.try_index_at(self.map_pixel(pos)) {
+            Some(self.pixels.get(idx))
         } else {
             None
         }
     }
 
+    /// Returns the pixel for modification and records it as damaged. Returns None if the pixel
+    /// is outside of the canvas or completely clipped. Note: Partial clip coverage is not applied,
+    /// use `try_set_color` to respect it.
     pub fn try_get_color_mut(&mut self, pos: [i32; 2]) -> Option<&mut Rgba> {
-        if let Some(idx) = self.try_index_at(pos) {
-            Some(self.img.get_mut(idx))
-        } else {
-            None
+        let pos = self.map_pixel(pos);
+        let idx = self.try_index_at(pos)?;
+        if let Some(clip) = &self.clip {
+            if clip.get(pos) == 0 {
+                return None;
+            }
         }
+        self.add_damage(Rect::new(pos, [1, 1]));
+        Some(self.pixels.get_mut(idx))
     }
 
     pub fn try_set_color(&mut self, pos: [i32; 2], c: Rgba) -> bool {
-        if let Some(idx) = self.try_index_at(pos) {
-            self.img.set(idx, c);
-            true
-        } else {
-            false
-        }
+        let pos = self.map_pixel(pos);
+        self.add_damage(Rect::new(pos, [1, 1]));
+        self.modify_pixel(pos, |pixel| *pixel = c)
     }
 
     pub fn try_blend_color(&mut self, pos: [i32; 2], c: Rgba) -> bool {
@@ -200,31 +554,49 @@ impl<'a> Canvas<'a> {
         pos: [i32; 2],
         c: Rgba,
     ) -> bool {
-        if let Some(idx) = self.try_index_at(pos) {
-            self.img.blend_using(mode, idx, c);
-            true
-        } else {
-            false
-        }
+        let cc = mode.prepare_color(c);
+        let pos = self.map_pixel(pos);
+        self.add_damage(Rect::new(pos, [1, 1]));
+        self.modify_pixel(pos, |pixel| mode.blend_color(pixel, &cc))
     }
 
     pub fn clear(&mut self, c: Rgba) {
         self.fill_using(ColorBlendOverwrite, c);
     }
 
-    pub fn fill(&mut self, c: Rgba) {
-        if c.alpha() == 255 {
-            self.fill_using(ColorBlendOverwrite, c);
+    pub fn fill<P: Paint>(&mut self, paint: P) {
+        if paint.is_opaque() {
+            self.fill_using(ColorBlendOverwrite, paint);
         } else {
-            self.fill_using(ColorBlendTransparent, c);
+            self.fill_using(ColorBlendTransparent, paint);
         }
     }
 
-    pub fn fill_using<Mode: ColorBlendMode>(&mut self, mode: Mode, c: Rgba) {
-        let cc = mode.prepare_color(c);
-        for row in self.rows_iter_mut() {
-            for pixel in row {
-                mode.blend_color(pixel, &cc);
+    pub fn fill_using<Mode: ColorBlendMode, P: Paint>(&mut self, mode: Mode, paint: P) {
+        let paint = self.local_paint(&paint);
+        self.add_damage(self.rect());
+        if self.clip.is_some() {
+            let rect = self.rect();
+            for y in rect.y()..rect.end_y() {
+                for x in rect.x()..rect.end_x() {
+                    self.modify_pixel([x, y], |pixel| {
+                        mode.blend_color(pixel, &mode.prepare_color(paint.color_at([x, y])))
+                    });
+                }
+            }
+        } else if let Some(c) = paint.solid_color() {
+            let cc = mode.prepare_color(c);
+            for row in self.rows_iter_mut() {
+                for pixel in row {
+                    mode.blend_color(pixel, &cc);
+                }
+            }
+        } else {
+            for ([x0, y], row) in self.rows_iter_mut().with_pos() {
+                for (x, pixel) in (x0..).zip(row.iter_mut()) {
+                    let cc = mode.prepare_color(paint.color_at([x, y]));
+                    mode.blend_color(pixel, &cc);
+                }
             }
         }
     }
@@ -246,38 +618,278 @@ impl<'a> Canvas<'a> {
         dim: [u32; 2],
         c: Rgba,
     ) {
-        let [x, y] = pos;
-        let [w, h] = dim;
+        let rect = match self.map_rect(Rect::new(pos, dim)) {
+            Some(rect) => rect,
+            None => {
+                // Note: Rotated outlines connect the centers of the corner pixels
+                let [x0, y0] = [pos[0] as f32 + 0.5, pos[1] as f32 + 0.5];
+                let [x1, y1] = [x0 + dim[0] as f32 - 1.0, y0 + dim[1] as f32 - 1.0];
+                let corners = [[x0, y0], [x1, y0], [x1, y1], [x0, y1]];
+                for i in 0..4 {
+                    let (p0, p1) = (corners[i], corners[(i + 1) % 4]);
+                    self.draw_line_using(mode, p0, p1, LineMode::Aliased, c);
+                }
+                return;
+            }
+        };
+        self.add_damage(rect);
+        let [x, y] = rect.pos;
+        let [w, h] = rect.dim;
+        let cc = mode.prepare_color(c);
         // TODO This is a slow implementation, make it fast
         let x_end = x + (w as i32) - 1;
         let y_end = y + (h as i32) - 1;
         for rx in x..(x_end + 1) {
-            self.try_blend_color_using(mode, [rx, y], c);
-            self.try_blend_color_using(mode, [rx, y_end], c);
+            self.modify_pixel([rx, y], |pixel| mode.blend_color(pixel, &cc));
+            self.modify_pixel([rx, y_end], |pixel| mode.blend_color(pixel, &cc));
         }
         for ry in (y + 1)..y_end {
-            self.try_blend_color_using(mode, [x, ry], c);
-            self.try_blend_color_using(mode, [x_end, ry], c);
+            self.modify_pixel([x, ry], |pixel| mode.blend_color(pixel, &cc));
+            self.modify_pixel([x_end, ry], |pixel| mode.blend_color(pixel, &cc));
         }
     }
 
-    pub fn fill_rect(&mut self, pos: [i32; 2], dim: [u32; 2], c: Rgba) {
-        if c.alpha() == 255 {
-            self.fill_rect_using(ColorBlendOverwrite, pos, dim, c);
+    pub fn draw_line(&mut self, p0: [f32; 2], p1: [f32; 2], line_mode: LineMode, c: Rgba) {
+        if c.alpha() == 255 && line_mode == LineMode::Aliased {
+            self.draw_line_using(ColorBlendOverwrite, p0, p1, line_mode, c);
         } else {
-            self.fill_rect_using(ColorBlendTransparent, pos, dim, c);
+            // Anti-aliased pixels are partially transparent even for a solid color
+            self.draw_line_using(ColorBlendTransparent, p0, p1, line_mode, c);
         }
     }
 
-    pub fn fill_rect_using<Mode: ColorBlendMode>(
+    /// Draws a one pixel wide line between the two points. Pixel centers are located at
+    /// `x + 0.5`, `y + 0.5`. For anti-aliased lines, the alpha of the color is scaled by the
+    /// pixel coverage before it is passed to the blend mode.
+    pub fn draw_line_using<Mode: ColorBlendMode>(
+        &mut self,
+        mode: Mode,
+        p0: [f32; 2],
+        p1: [f32; 2],
+        line_mode: LineMode,
+        c: Rgba,
+    ) {
+        let bounds = self.rect();
+        let p0 = self.transform.transform_point(p0);
+        let p1 = self.transform.transform_point(p1);
+        if let Some(rect) = bounding_rect(std::iter::once(&[p0, p1][..])) {
+            // Note: Anti-aliased lines extend up to one pixel beyond the end points
+            self.add_damage(rect.with_delta_pos([-1, -1]).with_delta_dim([2, 2]));
+        }
+        match line_mode {
+            LineMode::Aliased => {
+                let cc = mode.prepare_color(c);
+                plot_aliased_line(p0, p1, bounds, |pos| {
+                    self.modify_pixel(pos, |pixel| mode.blend_color(pixel, &cc));
+                });
+            }
+            LineMode::AntiAliased => {
+                plot_antialiased_line(p0, p1, bounds, |pos, alpha| {
+                    let cc = mode.prepare_color(c.relative_alpha(alpha));
+                    self.modify_pixel(pos, |pixel| mode.blend_color(pixel, &cc));
+                });
+            }
+        }
+    }
+
+    pub fn stroke_polyline<P: Paint>(
+        &mut self,
+        points: &[[f32; 2]],
+        style: &StrokeStyle,
+        paint: P,
+    ) {
+        self.stroke_polyline_using(ColorAlphaBlendTransparent, points, style, paint);
+    }
+
+    /// Draws an anti-aliased stroke along the connected line segments. The polyline is not
+    /// closed, the caps of the style are applied to the first and last point.
+    pub fn stroke_polyline_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        points: &[[f32; 2]],
+        style: &StrokeStyle,
+        paint: P,
+    ) {
+        let outline = stroke_outline(points, false, style);
+        self.fill_polygon_using(mode, &outline, FillRule::NonZero, paint);
+    }
+
+    pub fn stroke_line<P: Paint>(
+        &mut self,
+        p0: [f32; 2],
+        p1: [f32; 2],
+        style: &StrokeStyle,
+        paint: P,
+    ) {
+        self.stroke_line_using(ColorAlphaBlendTransparent, p0, p1, style, paint);
+    }
+
+    /// Draws an anti-aliased line with the width, caps and dash pattern of the style.
+    pub fn stroke_line_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        p0: [f32; 2],
+        p1: [f32; 2],
+        style: &StrokeStyle,
+        paint: P,
+    ) {
+        self.stroke_polyline_using(mode, &[p0, p1], style, paint);
+    }
+
+    pub fn stroke_rect<P: Paint>(&mut self, rect: Rect, style: &StrokeStyle, paint: P) {
+        self.stroke_rect_using(ColorAlphaBlendTransparent, rect, style, paint);
+    }
+
+    /// Draws the outline of the rectangle using the style. Like `draw_rect`, the outline stays
+    /// within the rectangle. The dash pattern starts at the top left corner and runs clockwise.
+    pub fn stroke_rect_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        rect: Rect,
+        style: &StrokeStyle,
+        paint: P,
+    ) {
+        let hw = 0.5 * style.width;
+        let (x0, y0) = (rect.x() as f32 + hw, rect.y() as f32 + hw);
+        let (x1, y1) = (rect.end_x() as f32 - hw, rect.end_y() as f32 - hw);
+        let points = [[x0, y0], [x1, y0], [x1, y1], [x0, y1]];
+        let outline = stroke_outline(&points, true, style);
+        self.fill_polygon_using(mode, &outline, FillRule::NonZero, paint);
+    }
+
+    pub fn fill_polygon<C: AsRef<[[f32; 2]]>, P: Paint>(
+        &mut self,
+        contours: &[C],
+        rule: FillRule,
+        paint: P,
+    ) {
+        self.fill_polygon_using(ColorAlphaBlendTransparent, contours, rule, paint);
+    }
+
+    /// Fills the area enclosed by the contours using exact-area anti-aliasing. Each contour is
+    /// implicitly closed. Multiple contours can be used to create holes, see `FillRule`.
+    pub fn fill_polygon_using<Mode: ColorAlphaBlendMode, C: AsRef<[[f32; 2]]>, P: Paint>(
+        &mut self,
+        mode: Mode,
+        contours: &[C],
+        rule: FillRule,
+        paint: P,
+    ) {
+        let raster = if self.integer_offset() == Some([0, 0]) {
+            Rasterizer::for_contours(self.rect(), contours)
+        } else {
+            let transformed: Vec<Vec<[f32; 2]>> = contours
+                .iter()
+                .map(|c| {
+                    c.as_ref()
+                        .iter()
+                        .map(|p| self.transform.transform_point(*p))
+                        .collect()
+                })
+                .collect();
+            Rasterizer::for_contours(self.rect(), &transformed)
+        };
+        if let Some(raster) = raster {
+            self.add_damage(raster.rect());
+            self.blend_coverage_using(mode, &raster, rule, paint);
+        }
+    }
+
+    pub fn fill_path<P: Paint>(&mut self, path: &Path, rule: FillRule, paint: P) {
+        self.fill_path_using(ColorAlphaBlendTransparent, path, rule, paint);
+    }
+
+    pub fn fill_path_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        path: &Path,
+        rule: FillRule,
+        paint: P,
+    ) {
+        let contours: Vec<_> = path.flatten().into_iter().map(|c| c.pts).collect();
+        self.fill_polygon_using(mode, &contours, rule, paint);
+    }
+
+    pub fn stroke_path<P: Paint>(&mut self, path: &Path, style: &StrokeStyle, paint: P) {
+        self.stroke_path_using(ColorAlphaBlendTransparent, path, style, paint);
+    }
+
+    pub fn stroke_path_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        path: &Path,
+        style: &StrokeStyle,
+        paint: P,
+    ) {
+        let mut outline = Vec::new();
+        for contour in path.flatten() {
+            outline.extend(stroke_outline(&contour.pts, contour.closed, style));
+        }
+        self.fill_polygon_using(mode, &outline, FillRule::NonZero, paint);
+    }
+
+    fn blend_coverage_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        raster: &Rasterizer,
+        rule: FillRule,
+        paint: P,
+    ) {
+        let paint = self.local_paint(&paint);
+        if let Some(c) = paint.solid_color() {
+            let cc = mode.prepare_color(c);
+            raster.for_each_coverage(rule, |pos, alpha| {
+                self.modify_pixel(pos, |bg| {
+                    if alpha == 255 {
+                        mode.blend_solid_color(bg, &cc);
+                    } else {
+                        mode.blend_color(bg, &cc, alpha);
+                    }
+                });
+            });
+        } else {
+            raster.for_each_coverage(rule, |pos, alpha| {
+                self.modify_pixel(pos, |bg| {
+                    mode.blend_color(bg, &mode.prepare_color(paint.color_at(pos)), alpha)
+                });
+            });
+        }
+    }
+
+    pub fn fill_rect<P: Paint>(&mut self, pos: [i32; 2], dim: [u32; 2], paint: P) {
+        if paint.is_opaque() {
+            self.fill_rect_using(ColorBlendOverwrite, pos, dim, paint);
+        } else {
+            self.fill_rect_using(ColorBlendTransparent, pos, dim, paint);
+        }
+    }
+
+    pub fn fill_rect_using<Mode: ColorBlendMode, P: Paint>(
         &mut self,
         mode: Mode,
         pos: [i32; 2],
         dim: [u32; 2],
-        c: Rgba,
+        paint: P,
     ) {
-        if let Some(mut sr) = self.sub_canvas(pos, dim) {
-            sr.fill_using(mode, c);
+        let rect = match self.map_rect(Rect::new(pos, dim)) {
+            Some(rect) => rect,
+            None => {
+                // Note: Rotated rectangles are anti-aliased like polygons
+                let corners = self.map_corners(Rect::new(pos, dim));
+                let paint = self.local_paint(&paint);
+                if let Some(raster) = Rasterizer::for_contours(self.rect(), &[corners]) {
+                    self.add_damage(raster.rect());
+                    raster.for_each_coverage(FillRule::NonZero, |pos, alpha| {
+                        let c = paint.color_at(pos).relative_alpha(alpha);
+                        let cc = mode.prepare_color(c);
+                        self.modify_pixel(pos, |pixel| mode.blend_color(pixel, &cc));
+                    });
+                }
+                return;
+            }
+        };
+        if let Some(mut sr) = self.sub_canvas_rect(rect) {
+            sr.fill_using(mode, paint);
         }
     }
 
@@ -291,14 +903,223 @@ impl<'a> Canvas<'a> {
         img: &Image,
         pos: [i32; 2],
     ) {
-        let [x, y] = pos;
-        for src_y in 0..img.height() {
-            for src_x in 0..img.width() {
-                let src = img.get([src_x, src_y]);
-                if let Some(dst) = self.try_get_color_mut([x + (src_x as i32), y + (src_y as i32)])
-                {
-                    mode.blend_color(dst, src);
+        self.draw_image_region_using(mode, img, Rect::new([0, 0], img.dim()), pos);
+    }
+
+    /// Draws the image with every pixel multiplied by the tint color and the opacity (0.0 to
+    /// 1.0). Use `ImageBlendTinted` with the other image drawing methods.
+    pub fn draw_image_tinted(&mut self, img: &Image, pos: [i32; 2], tint: Rgba, opacity: f32) {
+        let mode = ImageBlendTinted::new(ImageBlendTransparent, tint).with_opacity(opacity);
+        self.draw_image_using(mode, img, pos)
+    }
+
+    pub fn draw_image_region(&mut self, img: &Image, src_rect: Rect, pos: [i32; 2]) {
+        self.draw_image_region_using(ImageBlendTransparent, img, src_rect, pos)
+    }
+
+    /// Draws the part of the image within `src_rect` (for example a sprite of a sprite sheet),
+    /// its top-left corner is placed at `pos`.
+    pub fn draw_image_region_using<Mode: ImageBlendMode>(
+        &mut self,
+        mode: Mode,
+        img: &Image,
+        src_rect: Rect,
+        pos: [i32; 2],
+    ) {
+        let region = match src_rect.intersect(Rect::new([0, 0], img.dim())) {
+            Some(region) => region,
+            None => return,
+        };
+        let pos = match self.integer_offset() {
+            Some([dx, dy]) => [pos[0] + dx, pos[1] + dy],
+            None => {
+                let [x, y] = [pos[0] - src_rect.x(), pos[1] - src_rect.y()];
+                let bounds = Rect::new([region.x() + x, region.y() + y], region.dim);
+                let transform = Affine::translate(x as f32, y as f32);
+                let filter = self.transform_filter();
+                let edge = SampleEdge::Transparent;
+                self.draw_sampled_using(mode, img, region, transform, bounds, filter, edge);
+                return;
+            }
+        };
+        // Note: Clips the destination once, then blends whole rows
+        let offset = [pos[0] - src_rect.x(), pos[1] - src_rect.y()];
+        let dst = Rect::new([region.x() + offset[0], region.y() + offset[1]], region.dim);
+        let dst = match dst.intersect(self.rect()) {
+            Some(dst) => dst,
+            None => return,
+        };
+        self.add_damage(dst);
+        if self.clip.is_some() {
+            // Note: Blends pixel by pixel, so each one can be scaled by the clip mask
+            for y in dst.y()..dst.end_y() {
+                for x in dst.x()..dst.end_x() {
+                    let c = img.get([x - offset[0], y - offset[1]]);
+                    self.modify_pixel([x, y], |pixel| mode.blend_color(pixel, c));
+                }
+            }
+            return;
+        }
+        let width = dst.width() as usize;
+        let src_x = (dst.x() - offset[0]) as usize;
+        let src_stride = img.stride();
+        for y in dst.y()..dst.end_y() {
+            let src_idx = src_x + ((y - offset[1]) as usize) * src_stride;
+            let dst_idx = self.idx0
+                + ((dst.x() - self.pos[0]) as usize)
+                + ((y - self.pos[1]) as usize) * self.stride;
+            mode.blend_row(
+                self.pixels.slice_mut(dst_idx, width),
+                &img.buffer()[src_idx..(src_idx + width)],
+            );
+        }
+    }
+
+    pub fn draw_image_region_scaled(
+        &mut self,
+        img: &Image,
+        src_rect: Rect,
+        dst_rect: Rect,
+        filter: SampleFilter,
+    ) {
+        self.draw_image_region_scaled_using(ImageBlendTransparent, img, src_rect, dst_rect, filter)
+    }
+
+    /// Draws the part of the image within `src_rect` scaled to fill `dst_rect`. Only pixels of the
+    /// source rectangle are sampled, so neighboring sprites of a sprite sheet do not bleed in.
+    pub fn draw_image_region_scaled_using<Mode: ImageBlendMode>(
+        &mut self,
+        mode: Mode,
+        img: &Image,
+        src_rect: Rect,
+        dst_rect: Rect,
+        filter: SampleFilter,
+    ) {
+        if src_rect.is_empty() || dst_rect.is_empty() {
+            return;
+        }
+        let region = match src_rect.intersect(Rect::new([0, 0], img.dim())) {
+            Some(region) => region,
+            None => return,
+        };
+        let transform = Affine::translate(-src_rect.x() as f32, -src_rect.y() as f32)
+            .then_scale(
+                dst_rect.width() as f32 / src_rect.width() as f32,
+                dst_rect.height() as f32 / src_rect.height() as f32,
+            )
+            .then_translate(dst_rect.x() as f32, dst_rect.y() as f32);
+        self.draw_sampled_using(
+            mode,
+            img,
+            region,
+            transform,
+            dst_rect,
+            filter,
+            SampleEdge::Transparent,
+        );
+    }
+
+    pub fn draw_image_transformed(&mut self, img: &Image, transform: Affine, filter: SampleFilter) {
+        self.draw_image_transformed_using(ImageBlendTransparent, img, transform, filter)
+    }
+
+    /// Draws the image using the transformation, which maps image positions to canvas positions.
+    /// Every canvas pixel covered by the image is mapped back to the image and sampled using the
+    /// filter.
+    pub fn draw_image_transformed_using<Mode: ImageBlendMode>(
+        &mut self,
+        mode: Mode,
+        img: &Image,
+        transform: Affine,
+        filter: SampleFilter,
+    ) {
+        let [w, h] = [img.width() as f32, img.height() as f32];
+        let corners = [[0.0, 0.0], [w, 0.0], [w, h], [0.0, h]];
+        let corners: Vec<[f32; 2]> = corners
+            .iter()
+            .map(|p| transform.transform_point(*p))
+            .collect();
+        let bounds = match bounding_rect(std::iter::once(&corners[..])) {
+            Some(rect) => rect,
+            None => return,
+        };
+        let region = Rect::new([0, 0], img.dim());
+        self.draw_sampled_using(
+            mode,
+            img,
+            region,
+            transform,
+            bounds,
+            filter,
+            SampleEdge::Transparent,
+        );
+    }
+
+    /// Filter for images drawn at their original size, pixels stay sharp when the transformation
+    /// scales by whole numbers.
+    fn transform_filter(&self) -> SampleFilter {
+        let [a, b, c, d, e, f] = self.transform.0;
+        let whole = |v: f32| v.fract() == 0.0;
+        if b == 0.0 && c == 0.0 && [a, d, e, f].iter().all(|v| whole(*v)) {
+            SampleFilter::Nearest
+        } else {
+            SampleFilter::Bilinear
+        }
+    }
+
+    /// Samples the region of the image for every pixel within the bounds. The transformation and
+    /// the bounds use local coordinates.
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn draw_sampled_using<Mode: ImageBlendMode>(
+        &mut self,
+        mode: Mode,
+        img: &Image,
+        region: Rect,
+        transform: Affine,
+        bounds: Rect,
+        filter: SampleFilter,
+        edge: SampleEdge,
+    ) {
+        self.for_each_sample(img, region, transform, bounds, filter, edge, |pixel, c| {
+            mode.blend_color(pixel, c)
+        });
+    }
+
+    /// Samples the region of the image for every pixel within the transformed bounds and calls
+    /// `blend` with the pixel and the sampled color.
+    #[allow(clippy::too_many_arguments)]
+    fn for_each_sample<F: FnMut(&mut Rgba, Rgba)>(
+        &mut self,
+        img: &Image,
+        region: Rect,
+        transform: Affine,
+        bounds: Rect,
+        filter: SampleFilter,
+        edge: SampleEdge,
+        mut blend: F,
+    ) {
+        let transform = transform.then(self.transform);
+        let inv = match transform.invert() {
+            Some(inv) => inv,
+            None => return,
+        };
+        let bounds = match self.map_bounds(bounds) {
+            Some(bounds) => bounds,
+            None => return,
+        };
+        let rect = match bounds.intersect(self.rect()) {
+            Some(rect) => rect,
+            None => return,
+        };
+        self.add_damage(rect);
+        let step = inv.transform_vector([1.0, 0.0]);
+        for y in rect.y()..rect.end_y() {
+            let mut src = inv.transform_point([rect.x() as f32 + 0.5, y as f32 + 0.5]);
+            for x in rect.x()..rect.end_x() {
+                if let Some(c) = sample(img, region, src, filter, edge) {
+                    self.modify_pixel([x, y], |pixel| blend(pixel, c));
                 }
+                src = [src[0] + step[0], src[1] + step[1]];
             }
         }
     }
@@ -312,6 +1133,109 @@ impl<'a> Canvas<'a> {
         pos: [i32; 2],
         width: Option<u32>,
     ) {
-        font.render(font_size, font_color, txt, pos, width, self);
+        if self.integer_offset().is_some() {
+            font.render(font_size, font_color, txt, pos, width, self);
+        } else if let Some(rect) = font.render_bounds(font_size, txt, pos, width) {
+            self.draw_offscreen(ColorBlendTransparent, rect, font_color, |c, color| {
+                font.render(font_size, color, txt, pos, width, c);
+            });
+        }
+    }
+
+    /// Draws the text using the blend mode, for example `ColorBlendLinear` for gamma-correct
+    /// anti-aliasing.
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_using<Mode: ColorBlendMode>(
+        &mut self,
+        mode: Mode,
+        font: &mut Font,
+        font_size: u32,
+        font_color: Rgba,
+        txt: &str,
+        pos: [i32; 2],
+        width: Option<u32>,
+    ) {
+        if self.integer_offset().is_some() {
+            font.render_using(mode, font_size, font_color, txt, pos, width, self);
+        } else if let Some(rect) = font.render_bounds(font_size, txt, pos, width) {
+            self.draw_offscreen(mode, rect, font_color, |c, color| {
+                font.render(font_size, color, txt, pos, width, c);
+            });
+        }
+    }
+
+    /// Draws onto a transparent offscreen canvas without a transformation, which is then
+    /// resampled onto this canvas. Used for pixel based drawing like text when the
+    /// transformation scales or rotates. The offscreen canvas covers the part of `rect` (local
+    /// coordinates) that is visible on this canvas.
+    ///
+    /// `f` draws the coverage using the opaque color it is called with. The resampled coverage
+    /// is then applied to `color` and blended using `mode`, like drawing onto this canvas
+    /// directly would.
+    fn draw_offscreen<Mode: ColorBlendMode, F: FnOnce(&mut Canvas, Rgba)>(
+        &mut self,
+        mode: Mode,
+        rect: Rect,
+        color: Rgba,
+        f: F,
+    ) {
+        let inv = match self.transform.invert() {
+            Some(inv) => inv,
+            None => return,
+        };
+        let [x0, y0] = [self.pos[0] as f32, self.pos[1] as f32];
+        let [x1, y1] = [x0 + self.dim[0] as f32, y0 + self.dim[1] as f32];
+        let corners = [[x0, y0], [x1, y0], [x1, y1], [x0, y1]].map(|p| inv.transform_point(p));
+        let local = match bounding_rect(std::iter::once(&corners[..])) {
+            Some(visible) => match visible.intersect(rect) {
+                Some(local) if !local.is_empty() => local,
+                _ => return,
+            },
+            None => return,
+        };
+        let mut img = Image::new(local.dim);
+        f(
+            &mut Canvas::new(&mut img, 0, local.pos, local.dim),
+            Rgba([255, 255, 255, 255]),
+        );
+        let region = Rect::new([0, 0], local.dim);
+        let transform = Affine::translate(local.x() as f32, local.y() as f32);
+        self.for_each_sample(
+            &img,
+            region,
+            transform,
+            local,
+            SampleFilter::Bilinear,
+            SampleEdge::Transparent,
+            |pixel, c| {
+                if c.alpha() > 0 {
+                    mode.blend_color(pixel, &mode.prepare_color(color.relative_alpha(c.alpha())));
+                }
+            },
+        );
+    }
+}
+
+/// Paint with local coordinates, which are mapped back from the coordinate space of the image.
+pub(crate) struct LocalPaint<'p, P: Paint> {
+    paint: &'p P,
+    inv: Option<Affine>,
+}
+
+impl<'p, P: Paint> Paint for LocalPaint<'p, P> {
+    fn color_at(&self, pos: [i32; 2]) -> Rgba {
+        match self.inv {
+            Some(inv) => {
+                let p = inv.transform_point([pos[0] as f32 + 0.5, pos[1] as f32 + 0.5]);
+                self.paint.color_at_point(p)
+            }
+            None => self.paint.color_at(pos),
+        }
+    }
+    fn solid_color(&self) -> Option<Rgba> {
+        self.paint.solid_color()
+    }
+    fn is_opaque(&self) -> bool {
+        self.paint.is_opaque()
     }
 }
@@ -0,0 +1,103 @@
+use crate::rect::Rect;
+use std::sync::{Arc, Mutex};
+
+/// Default limit of the number of rectangles, see `DamageTracker::with_max_rects`.
+const DEFAULT_MAX_RECTS: usize = 8;
+
+/// Records the regions of an image that are changed by drawing operations, for example to only
+/// redraw or transmit the changed parts of a frame. Clones share the same list, so a tracker can
+/// be used by several canvases at once (including split canvases on other threads). Overlapping
+/// rectangles are merged, and once the limit is reached every new rectangle is merged into the
+/// one that grows the least.
+#[derive(Clone, Debug)]
+pub struct DamageTracker {
+    rects: Arc<Mutex<Vec<Rect>>>,
+    max_rects: usize,
+}
+
+impl Default for DamageTracker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DamageTracker {
+    pub fn new() -> Self {
+        Self {
+            rects: Arc::new(Mutex::new(Vec::new())),
+            max_rects: DEFAULT_MAX_RECTS,
+        }
+    }
+
+    /// Limits the number of rectangles, 1 tracks the union of every change.
+    pub fn with_max_rects(mut self, max_rects: usize) -> Self {
+        self.max_rects = max_rects.max(1);
+        self
+    }
+
+    pub fn max_rects(&self) -> usize {
+        self.max_rects
+    }
+
+    pub fn add(&self, rect: Rect) {
+        if rect.is_empty() {
+            return;
+        }
+        let mut rects = self.rects.lock().unwrap();
+        let mut rect = rect;
+        loop {
+            if rects.iter().any(|r| contains_rect(*r, rect)) {
+                return;
+            }
+            rects.retain(|r| !contains_rect(rect, *r));
+            let merge = match rects.iter().position(|r| r.intersect(rect).is_some()) {
+                Some(idx) => Some(idx),
+                None if rects.len() >= self.max_rects => (0..rects.len())
+                    .min_by_key(|idx| area(rects[*idx].union(rect)) - area(rects[*idx])),
+                None => None,
+            };
+            match merge {
+                Some(idx) => rect = rect.union(rects.swap_remove(idx)),
+                None => {
+                    rects.push(rect);
+                    return;
+                }
+            }
+        }
+    }
+
+    /// Rectangles that were changed since the last reset.
+    pub fn rects(&self) -> Vec<Rect> {
+        self.rects.lock().unwrap().clone()
+    }
+
+    /// Smallest rectangle that contains every change, or None if nothing changed.
+    pub fn bounding_rect(&self) -> Option<Rect> {
+        let rects = self.rects.lock().unwrap();
+        rects.iter().copied().reduce(|a, b| a.union(b))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rects.lock().unwrap().is_empty()
+    }
+
+    /// Returns the changed rectangles and resets the tracker.
+    pub fn take(&self) -> Vec<Rect> {
+        std::mem::take(&mut *self.rects.lock().unwrap())
+    }
+
+    pub fn reset(&self) {
+        self.rects.lock().unwrap().clear();
+    }
+}
+
+fn contains_rect(outer: Rect, inner: Rect) -> bool {
+    outer.x() <= inner.x()
+        && outer.y() <= inner.y()
+        && outer.end_x() >= inner.end_x()
+        && outer.end_y() >= inner.end_y()
+}
+
+fn area(rect: Rect) -> u64 {
+    (rect.width() as u64) * (rect.height() as u64)
+}
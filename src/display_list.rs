@@ -0,0 +1,695 @@
+use crate::affine::Affine;
+use crate::blend::{
+    ColorAlphaBlendLinear, ColorAlphaBlendOverwrite, ColorAlphaBlendTransparent, ColorBlendLinear,
+    ColorBlendOverwrite, ColorBlendTransparent, ImageBlendLinear, ImageBlendOverwrite,
+    ImageBlendTransparent, MixBlendMode, PorterDuff,
+};
+use crate::canvas::Canvas;
+use crate::font::Font;
+use crate::img::Image;
+use crate::line::LineMode;
+use crate::path::Path;
+use crate::raster::FillRule;
+use crate::rect::Rect;
+use crate::rgba::Rgba;
+use crate::sample::SampleFilter;
+use crate::stroke::StrokeStyle;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// Blend mode of a recorded command. Each variant selects the matching color, color alpha or
+/// image blend mode, for example `Transparent` uses `ColorBlendTransparent`,
+/// `ColorAlphaBlendTransparent` or `ImageBlendTransparent`.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug)]
+pub enum BlendMode {
+    Overwrite,
+    Transparent,
+    Linear,
+    PorterDuff(PorterDuff),
+    Mix(MixBlendMode),
+}
+
+/// Drawing command of a `DisplayList`. Positions use the coordinates of the canvas that the list
+/// is replayed onto (moved by the offset). If the mode is None, then the command uses the same
+/// mode as the default canvas method (for example `fill_rect` overwrites with opaque colors).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub enum DrawCommand {
+    Fill {
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    FillRect {
+        rect: Rect,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    DrawRect {
+        rect: Rect,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    DrawLine {
+        p0: [f32; 2],
+        p1: [f32; 2],
+        line_mode: LineMode,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    FillPolygon {
+        contours: Vec<Vec<[f32; 2]>>,
+        rule: FillRule,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    FillPath {
+        path: Path,
+        rule: FillRule,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    StrokePath {
+        path: Path,
+        style: StrokeStyle,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    FillCircle {
+        center: [f32; 2],
+        radius: f32,
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    FillRoundedRect {
+        rect: Rect,
+        radii: [f32; 4],
+        color: Rgba,
+        mode: Option<BlendMode>,
+    },
+    /// Draws the image with the index (see `DisplayList::images`).
+    DrawImage {
+        image: usize,
+        pos: [i32; 2],
+        mode: Option<BlendMode>,
+    },
+    DrawImageRegionScaled {
+        image: usize,
+        src_rect: Rect,
+        dst_rect: Rect,
+        filter: SampleFilter,
+        mode: Option<BlendMode>,
+    },
+    /// Draws the text using the font with the index of the fonts passed to `replay`.
+    DrawText {
+        font: usize,
+        font_size: u32,
+        color: Rgba,
+        text: String,
+        pos: [i32; 2],
+        width: Option<u32>,
+        mode: Option<BlendMode>,
+    },
+}
+
+/// Recorded list of drawing commands, which can be replayed onto any canvas (at an offset),
+/// compared, cached or serialized. The images of the commands are stored once in the list,
+/// fonts are passed to `replay` since they can not be serialized. Note: Only solid `Rgba` colors
+/// can be recorded, gradients, image patterns and closures can not be used as paint.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Default)]
+pub struct DisplayList {
+    commands: Vec<DrawCommand>,
+    images: Vec<Image>,
+    // Dimensions and hash of each image, used to find identical images in `add_image`
+    #[cfg_attr(feature = "serde", serde(skip))]
+    image_keys: Vec<([u32; 2], u64)>,
+}
+
+impl PartialEq for DisplayList {
+    fn eq(&self, other: &Self) -> bool {
+        self.commands == other.commands && self.images == other.images
+    }
+}
+
+// Note: Calls the canvas method with the blend mode type that matches the variant
+macro_rules! with_mode {
+    ($mode:expr, [$overwrite:expr, $transparent:expr, $linear:expr], |$m:ident| $call:expr) => {
+        match $mode {
+            BlendMode::Overwrite => {
+                let $m = $overwrite;
+                $call
+            }
+            BlendMode::Transparent => {
+                let $m = $transparent;
+                $call
+            }
+            BlendMode::Linear => {
+                let $m = $linear;
+                $call
+            }
+            BlendMode::PorterDuff(op) => {
+                let $m = op;
+                $call
+            }
+            BlendMode::Mix(op) => {
+                let $m = op;
+                $call
+            }
+        }
+    };
+}
+
+macro_rules! with_color_mode {
+    ($mode:expr, |$m:ident| $call:expr) => {
+        with_mode!(
+            $mode,
+            [ColorBlendOverwrite, ColorBlendTransparent, ColorBlendLinear],
+            |$m| $call
+        )
+    };
+}
+
+macro_rules! with_alpha_mode {
+    ($mode:expr, |$m:ident| $call:expr) => {
+        with_mode!(
+            $mode,
+            [
+                ColorAlphaBlendOverwrite,
+                ColorAlphaBlendTransparent,
+                ColorAlphaBlendLinear
+            ],
+            |$m| $call
+        )
+    };
+}
+
+macro_rules! with_image_mode {
+    ($mode:expr, |$m:ident| $call:expr) => {
+        with_mode!(
+            $mode,
+            [ImageBlendOverwrite, ImageBlendTransparent, ImageBlendLinear],
+            |$m| $call
+        )
+    };
+}
+
+impl DisplayList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn commands(&self) -> &[DrawCommand] {
+        &self.commands
+    }
+    pub fn images(&self) -> &[Image] {
+        &self.images
+    }
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.commands.clear();
+        self.images.clear();
+        self.image_keys.clear();
+    }
+
+    /// Adds the command, image indices must refer to images added with `add_image`. Returns false
+    /// (and ignores the command) if an image index is not valid. Font indices refer to the fonts
+    /// passed to `replay`, so they are only checked when the list is replayed.
+    pub fn push(&mut self, cmd: DrawCommand) -> bool {
+        match cmd {
+            DrawCommand::DrawImage { image, .. } | DrawCommand::DrawImageRegionScaled { image, .. }
+                if image >= self.images.len() =>
+            {
+                false
+            }
+            _ => {
+                self.commands.push(cmd);
+                true
+            }
+        }
+    }
+
+    /// Adds the image (unless the list already contains an identical one) and returns its index.
+    pub fn add_image(&mut self, img: &Image) -> usize {
+        // Note: Images of deserialized lists do not have keys yet
+        for idx in self.image_keys.len()..self.images.len() {
+            let key = image_key(&self.images[idx]);
+            self.image_keys.push(key);
+        }
+        let key = image_key(img);
+        let found = (0..self.images.len()).find(|idx| {
+            // Note: Only compares the pixels if the hash matches
+            self.image_keys[*idx] == key && self.images[*idx] == *img
+        });
+        match found {
+            Some(idx) => idx,
+            None => {
+                self.images.push(img.clone());
+                self.image_keys.push(key);
+                self.images.len() - 1
+            }
+        }
+    }
+
+    pub fn fill(&mut self, color: Rgba) {
+        self.push(DrawCommand::Fill { color, mode: None });
+    }
+    pub fn fill_using(&mut self, mode: BlendMode, color: Rgba) {
+        let mode = Some(mode);
+        self.push(DrawCommand::Fill { color, mode });
+    }
+
+    pub fn fill_rect(&mut self, pos: [i32; 2], dim: [u32; 2], color: Rgba) {
+        let rect = Rect::new(pos, dim);
+        self.push(DrawCommand::FillRect {
+            rect,
+            color,
+            mode: None,
+        });
+    }
+    pub fn fill_rect_using(&mut self, mode: BlendMode, pos: [i32; 2], dim: [u32; 2], color: Rgba) {
+        let (rect, mode) = (Rect::new(pos, dim), Some(mode));
+        self.push(DrawCommand::FillRect { rect, color, mode });
+    }
+
+    pub fn draw_rect(&mut self, pos: [i32; 2], dim: [u32; 2], color: Rgba) {
+        let rect = Rect::new(pos, dim);
+        self.push(DrawCommand::DrawRect {
+            rect,
+            color,
+            mode: None,
+        });
+    }
+    pub fn draw_rect_using(&mut self, mode: BlendMode, pos: [i32; 2], dim: [u32; 2], color: Rgba) {
+        let (rect, mode) = (Rect::new(pos, dim), Some(mode));
+        self.push(DrawCommand::DrawRect { rect, color, mode });
+    }
+
+    pub fn draw_line(&mut self, p0: [f32; 2], p1: [f32; 2], line_mode: LineMode, color: Rgba) {
+        self.draw_line_with(None, p0, p1, line_mode, color);
+    }
+    pub fn draw_line_using(
+        &mut self,
+        mode: BlendMode,
+        p0: [f32; 2],
+        p1: [f32; 2],
+        line_mode: LineMode,
+        color: Rgba,
+    ) {
+        self.draw_line_with(Some(mode), p0, p1, line_mode, color);
+    }
+    fn draw_line_with(
+        &mut self,
+        mode: Option<BlendMode>,
+        p0: [f32; 2],
+        p1: [f32; 2],
+        line_mode: LineMode,
+        color: Rgba,
+    ) {
+        self.push(DrawCommand::DrawLine {
+            p0,
+            p1,
+            line_mode,
+            color,
+            mode,
+        });
+    }
+
+    pub fn fill_polygon<C: AsRef<[[f32; 2]]>>(
+        &mut self,
+        contours: &[C],
+        rule: FillRule,
+        color: Rgba,
+    ) {
+        self.fill_polygon_with(None, contours, rule, color);
+    }
+    pub fn fill_polygon_using<C: AsRef<[[f32; 2]]>>(
+        &mut self,
+        mode: BlendMode,
+        contours: &[C],
+        rule: FillRule,
+        color: Rgba,
+    ) {
+        self.fill_polygon_with(Some(mode), contours, rule, color);
+    }
+    fn fill_polygon_with<C: AsRef<[[f32; 2]]>>(
+        &mut self,
+        mode: Option<BlendMode>,
+        contours: &[C],
+        rule: FillRule,
+        color: Rgba,
+    ) {
+        let contours = contours.iter().map(|c| c.as_ref().to_vec()).collect();
+        self.push(DrawCommand::FillPolygon {
+            contours,
+            rule,
+            color,
+            mode,
+        });
+    }
+
+    pub fn fill_path(&mut self, path: &Path, rule: FillRule, color: Rgba) {
+        let path = path.clone();
+        self.push(DrawCommand::FillPath {
+            path,
+            rule,
+            color,
+            mode: None,
+        });
+    }
+    pub fn fill_path_using(&mut self, mode: BlendMode, path: &Path, rule: FillRule, color: Rgba) {
+        let (path, mode) = (path.clone(), Some(mode));
+        self.push(DrawCommand::FillPath {
+            path,
+            rule,
+            color,
+            mode,
+        });
+    }
+
+    pub fn stroke_path(&mut self, path: &Path, style: &StrokeStyle, color: Rgba) {
+        let (path, style) = (path.clone(), style.clone());
+        self.push(DrawCommand::StrokePath {
+            path,
+            style,
+            color,
+            mode: None,
+        });
+    }
+    pub fn stroke_path_using(
+        &mut self,
+        mode: BlendMode,
+        path: &Path,
+        style: &StrokeStyle,
+        color: Rgba,
+    ) {
+        let (path, style, mode) = (path.clone(), style.clone(), Some(mode));
+        self.push(DrawCommand::StrokePath {
+            path,
+            style,
+            color,
+            mode,
+        });
+    }
+
+    pub fn fill_circle(&mut self, center: [f32; 2], radius: f32, color: Rgba) {
+        self.push(DrawCommand::FillCircle {
+            center,
+            radius,
+            color,
+            mode: None,
+        });
+    }
+    pub fn fill_circle_using(
+        &mut self,
+        mode: BlendMode,
+        center: [f32; 2],
+        radius: f32,
+        color: Rgba,
+    ) {
+        let mode = Some(mode);
+        self.push(DrawCommand::FillCircle {
+            center,
+            radius,
+            color,
+            mode,
+        });
+    }
+
+    pub fn fill_rounded_rect(&mut self, rect: Rect, radii: [f32; 4], color: Rgba) {
+        self.push(DrawCommand::FillRoundedRect {
+            rect,
+            radii,
+            color,
+            mode: None,
+        });
+    }
+    pub fn fill_rounded_rect_using(
+        &mut self,
+        mode: BlendMode,
+        rect: Rect,
+        radii: [f32; 4],
+        color: Rgba,
+    ) {
+        let mode = Some(mode);
+        self.push(DrawCommand::FillRoundedRect {
+            rect,
+            radii,
+            color,
+            mode,
+        });
+    }
+
+    pub fn draw_image(&mut self, img: &Image, pos: [i32; 2]) {
+        let image = self.add_image(img);
+        self.push(DrawCommand::DrawImage {
+            image,
+            pos,
+            mode: None,
+        });
+    }
+    pub fn draw_image_using(&mut self, mode: BlendMode, img: &Image, pos: [i32; 2]) {
+        let (image, mode) = (self.add_image(img), Some(mode));
+        self.push(DrawCommand::DrawImage { image, pos, mode });
+    }
+
+    pub fn draw_image_region_scaled(
+        &mut self,
+        img: &Image,
+        src_rect: Rect,
+        dst_rect: Rect,
+        filter: SampleFilter,
+    ) {
+        self.draw_image_region_scaled_with(None, img, src_rect, dst_rect, filter);
+    }
+    pub fn draw_image_region_scaled_using(
+        &mut self,
+        mode: BlendMode,
+        img: &Image,
+        src_rect: Rect,
+        dst_rect: Rect,
+        filter: SampleFilter,
+    ) {
+        self.draw_image_region_scaled_with(Some(mode), img, src_rect, dst_rect, filter);
+    }
+    fn draw_image_region_scaled_with(
+        &mut self,
+        mode: Option<BlendMode>,
+        img: &Image,
+        src_rect: Rect,
+        dst_rect: Rect,
+        filter: SampleFilter,
+    ) {
+        let image = self.add_image(img);
+        self.push(DrawCommand::DrawImageRegionScaled {
+            image,
+            src_rect,
+            dst_rect,
+            filter,
+            mode,
+        });
+    }
+
+    /// Records the text, which is drawn with the font at the index of the fonts passed to
+    /// `replay`.
+    pub fn draw_text(
+        &mut self,
+        font: usize,
+        font_size: u32,
+        color: Rgba,
+        txt: &str,
+        pos: [i32; 2],
+        width: Option<u32>,
+    ) {
+        self.draw_text_with(None, font, font_size, color, txt, pos, width);
+    }
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_text_using(
+        &mut self,
+        mode: BlendMode,
+        font: usize,
+        font_size: u32,
+        color: Rgba,
+        txt: &str,
+        pos: [i32; 2],
+        width: Option<u32>,
+    ) {
+        self.draw_text_with(Some(mode), font, font_size, color, txt, pos, width);
+    }
+    #[allow(clippy::too_many_arguments)]
+    fn draw_text_with(
+        &mut self,
+        mode: Option<BlendMode>,
+        font: usize,
+        font_size: u32,
+        color: Rgba,
+        txt: &str,
+        pos: [i32; 2],
+        width: Option<u32>,
+    ) {
+        self.push(DrawCommand::DrawText {
+            font,
+            font_size,
+            color,
+            text: txt.to_string(),
+            pos,
+            width,
+            mode,
+        });
+    }
+
+    /// Draws the commands onto the canvas, every position is moved by the offset. Commands with an
+    /// image index that is not within `images` (for example from deserialized data) and text
+    /// commands whose font index is not within `fonts` are skipped.
+    pub fn replay(&self, c: &mut Canvas, offset: [i32; 2], fonts: &mut [Font]) {
+        let mut c = c.reborrow();
+        let translate = Affine::translate(offset[0] as f32, offset[1] as f32);
+        c.set_transform(translate.then(c.transform()));
+        for cmd in self.commands.iter() {
+            self.replay_command(&mut c, cmd, fonts);
+        }
+    }
+
+    fn replay_command(&self, c: &mut Canvas, cmd: &DrawCommand, fonts: &mut [Font]) {
+        match cmd {
+            DrawCommand::Fill { color, mode } => match mode {
+                Some(mode) => with_color_mode!(*mode, |m| c.fill_using(m, *color)),
+                None => c.fill(*color),
+            },
+            DrawCommand::FillRect { rect, color, mode } => match mode {
+                Some(mode) => {
+                    with_color_mode!(*mode, |m| c.fill_rect_using(m, rect.pos, rect.dim, *color))
+                }
+                None => c.fill_rect(rect.pos, rect.dim, *color),
+            },
+            DrawCommand::DrawRect { rect, color, mode } => match mode {
+                Some(mode) => {
+                    with_color_mode!(*mode, |m| c.draw_rect_using(m, rect.pos, rect.dim, *color))
+                }
+                None => c.draw_rect(rect.pos, rect.dim, *color),
+            },
+            DrawCommand::DrawLine {
+                p0,
+                p1,
+                line_mode,
+                color,
+                mode,
+            } => match mode {
+                Some(mode) => with_color_mode!(*mode, |m| c
+                    .draw_line_using(m, *p0, *p1, *line_mode, *color)),
+                None => c.draw_line(*p0, *p1, *line_mode, *color),
+            },
+            DrawCommand::FillPolygon {
+                contours,
+                rule,
+                color,
+                mode,
+            } => match mode {
+                Some(mode) => {
+                    with_alpha_mode!(*mode, |m| c.fill_polygon_using(m, contours, *rule, *color))
+                }
+                None => c.fill_polygon(contours, *rule, *color),
+            },
+            DrawCommand::FillPath {
+                path,
+                rule,
+                color,
+                mode,
+            } => match mode {
+                Some(mode) => {
+                    with_alpha_mode!(*mode, |m| c.fill_path_using(m, path, *rule, *color))
+                }
+                None => c.fill_path(path, *rule, *color),
+            },
+            DrawCommand::StrokePath {
+                path,
+                style,
+                color,
+                mode,
+            } => match mode {
+                Some(mode) => {
+                    with_alpha_mode!(*mode, |m| c.stroke_path_using(m, path, style, *color))
+                }
+                None => c.stroke_path(path, style, *color),
+            },
+            DrawCommand::FillCircle {
+                center,
+                radius,
+                color,
+                mode,
+            } => match mode {
+                Some(mode) => {
+                    with_alpha_mode!(*mode, |m| c.fill_circle_using(m, *center, *radius, *color))
+                }
+                None => c.fill_circle(*center, *radius, *color),
+            },
+            DrawCommand::FillRoundedRect {
+                rect,
+                radii,
+                color,
+                mode,
+            } => match mode {
+                Some(mode) => with_alpha_mode!(*mode, |m| c
+                    .fill_rounded_rect_using(m, *rect, *radii, *color)),
+                None => c.fill_rounded_rect(*rect, *radii, *color),
+            },
+            DrawCommand::DrawImage { image, pos, mode } => {
+                let img = match self.images.get(*image) {
+                    Some(img) => img,
+                    None => return,
+                };
+                match mode {
+                    Some(mode) => with_image_mode!(*mode, |m| c.draw_image_using(m, img, *pos)),
+                    None => c.draw_image(img, *pos),
+                }
+            }
+            DrawCommand::DrawImageRegionScaled {
+                image,
+                src_rect,
+                dst_rect,
+                filter,
+                mode,
+            } => {
+                let img = match self.images.get(*image) {
+                    Some(img) => img,
+                    None => return,
+                };
+                match mode {
+                    Some(mode) => with_image_mode!(*mode, |m| c
+                        .draw_image_region_scaled_using(m, img, *src_rect, *dst_rect, *filter)),
+                    None => c.draw_image_region_scaled(img, *src_rect, *dst_rect, *filter),
+                }
+            }
+            DrawCommand::DrawText {
+                font,
+                font_size,
+                color,
+                text,
+                pos,
+                width,
+                mode,
+            } => {
+                let font = match fonts.get_mut(*font) {
+                    Some(font) => font,
+                    None => return,
+                };
+                match mode {
+                    Some(mode) => with_color_mode!(*mode, |m| c
+                        .draw_text_using(m, font, *font_size, *color, text, *pos, *width)),
+                    None => c.draw_text(font, *font_size, *color, text, *pos, *width),
+                }
+            }
+        }
+    }
+}
+
+fn image_key(img: &Image) -> ([u32; 2], u64) {
+    let mut hasher = DefaultHasher::new();
+    img.hash(&mut hasher);
+    (img.dim(), hasher.finish())
+}
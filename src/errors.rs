@@ -93,3 +93,52 @@ impl std::error::Error for FontLoadError {
         }
     }
 }
+
+#[derive(Clone)]
+pub struct NinePatchError {
+    kind: NinePatchErrorKind,
+}
+#[derive(Clone)]
+enum NinePatchErrorKind {
+    TooSmall,
+    InvalidMarker([u32; 2]),
+    SplitStretchArea,
+}
+impl NinePatchError {
+    pub(crate) fn too_small() -> Self {
+        Self {
+            kind: NinePatchErrorKind::TooSmall,
+        }
+    }
+    pub(crate) fn invalid_marker(pos: [u32; 2]) -> Self {
+        Self {
+            kind: NinePatchErrorKind::InvalidMarker(pos),
+        }
+    }
+    pub(crate) fn split_stretch_area() -> Self {
+        Self {
+            kind: NinePatchErrorKind::SplitStretchArea,
+        }
+    }
+}
+impl std::fmt::Display for NinePatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match &self.kind {
+            NinePatchErrorKind::TooSmall => write!(f, "Nine-patch image is smaller than 3x3"),
+            NinePatchErrorKind::InvalidMarker(pos) => write!(
+                f,
+                "Nine-patch border pixel at {:?} is neither black nor transparent",
+                pos
+            ),
+            NinePatchErrorKind::SplitStretchArea => {
+                write!(f, "Nine-patch stretch markers are not contiguous")
+            }
+        }
+    }
+}
+impl std::fmt::Debug for NinePatchError {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+impl std::error::Error for NinePatchError {}
@@ -0,0 +1,206 @@
+use crate::blend::{ColorBlendMode, ColorBlendOverwrite, ColorBlendTransparent};
+use crate::canvas::Canvas;
+use crate::img::Image;
+use crate::mask::Mask;
+use crate::paint::Paint;
+use crate::rect::Rect;
+use crate::rgba::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Determines which neighbors of a pixel belong to the same region.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum Connectivity {
+    /// Horizontal and vertical neighbors.
+    Four,
+    /// Horizontal, vertical and diagonal neighbors.
+    Eight,
+}
+
+/// Parameters used to find the region of a flood fill.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct FloodFill {
+    /// Maximum distance between the color of the seed pixel and the color of a pixel within the
+    /// region. The distance is the euclidean distance of the RGBA values (0 to 255 each), a
+    /// tolerance of 0 only accepts the exact same color.
+    pub tolerance: f32,
+    pub connectivity: Connectivity,
+    /// If true, then the region stops at the bounds of the canvas. Otherwise the region can
+    /// extend across the whole image (only the part within the canvas is drawn).
+    pub within_canvas: bool,
+}
+impl FloodFill {
+    pub fn new(tolerance: f32, connectivity: Connectivity) -> Self {
+        Self {
+            tolerance,
+            connectivity,
+            within_canvas: true,
+        }
+    }
+    pub fn with_within_canvas(mut self, within_canvas: bool) -> Self {
+        self.within_canvas = within_canvas;
+        self
+    }
+}
+
+impl Image {
+    /// Finds the region that is connected to the seed pixel and has a similar color. The
+    /// rectangle of the returned mask is the bounding rectangle of the region. Returns None if
+    /// the seed is outside of the image.
+    pub fn flood_select(&self, seed: [i32; 2], params: &FloodFill) -> Option<Mask> {
+        let bounds = Rect::new([0, 0], self.dim());
+        flood_region(|pos| self.get(pos), bounds, seed, params)
+    }
+
+    /// Fills the region that is connected to the seed pixel and has a similar color, see
+    /// `Canvas::flood_fill`.
+    pub fn flood_fill(
+        &mut self,
+        seed: [i32; 2],
+        c: Rgba,
+        tolerance: f32,
+        connectivity: Connectivity,
+    ) -> Option<Mask> {
+        self.as_canvas()
+            .flood_fill(seed, c, tolerance, connectivity)
+    }
+}
+
+impl<'a> Canvas<'a> {
+    /// Finds the region that is connected to the seed pixel and has a similar color, without
+    /// drawing anything. See `Image::flood_select`. The seed is transformed like the other drawing
+    /// positions, the mask uses the coordinate space of the image.
+    pub fn flood_select(&self, seed: [i32; 2], params: &FloodFill) -> Option<Mask> {
+        let seed = self.map_pixel(seed);
+        let bounds = if params.within_canvas {
+            self.rect()
+        } else {
+            self.bounds()
+        };
+        flood_region(|pos| self.pixel_at(pos), bounds, seed, params)
+    }
+
+    /// Fills the region that is connected to the seed pixel and has a similar color (bucket
+    /// fill). Returns the filled region, the rectangle of the mask is its bounding rectangle.
+    pub fn flood_fill(
+        &mut self,
+        seed: [i32; 2],
+        c: Rgba,
+        tolerance: f32,
+        connectivity: Connectivity,
+    ) -> Option<Mask> {
+        let params = FloodFill::new(tolerance, connectivity);
+        if c.alpha() == 255 {
+            self.flood_fill_using(ColorBlendOverwrite, seed, c, &params)
+        } else {
+            self.flood_fill_using(ColorBlendTransparent, seed, c, &params)
+        }
+    }
+
+    pub fn flood_fill_using<Mode: ColorBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        seed: [i32; 2],
+        paint: P,
+        params: &FloodFill,
+    ) -> Option<Mask> {
+        let mask = self.flood_select(seed, params)?;
+        let paint = self.local_paint(&paint);
+        if let Some(rect) = mask.rect().intersect(self.rect()) {
+            self.add_damage(rect);
+            for y in rect.y()..rect.end_y() {
+                for x in rect.x()..rect.end_x() {
+                    if mask.get([x, y]) != 0 {
+                        let cc = mode.prepare_color(paint.color_at([x, y]));
+                        self.modify_pixel([x, y], |bg| mode.blend_color(bg, &cc));
+                    }
+                }
+            }
+        }
+        Some(mask)
+    }
+}
+
+/// Scanline flood fill, only pixels within the bounds are considered. `get` returns the color of
+/// a pixel within the bounds.
+fn flood_region<F: Fn([i32; 2]) -> Rgba>(
+    get: F,
+    bounds: Rect,
+    seed: [i32; 2],
+    params: &FloodFill,
+) -> Option<Mask> {
+    if !bounds.contains(seed) {
+        return None;
+    }
+    let target = get(seed).rgba();
+    let max_dist2 = if params.tolerance >= 0.0 {
+        params.tolerance * params.tolerance
+    } else {
+        -1.0
+    };
+    let matches = |x: usize, y: usize| {
+        let c = get([bounds.x() + (x as i32), bounds.y() + (y as i32)]).rgba();
+        let mut dist2 = 0.0;
+        for i in 0..4 {
+            let d = (c[i] as f32) - (target[i] as f32);
+            dist2 += d * d;
+        }
+        dist2 <= max_dist2
+    };
+
+    let w = bounds.width() as usize;
+    let h = bounds.height() as usize;
+    let mut mask = Mask::new(bounds);
+    let filled = mask.data_mut();
+    let diagonal = params.connectivity == Connectivity::Eight;
+    let mut stack = vec![(
+        (seed[0] - bounds.x()) as usize,
+        (seed[1] - bounds.y()) as usize,
+    )];
+    while let Some((x, y)) = stack.pop() {
+        if filled[x + y * w] != 0 || !matches(x, y) {
+            continue;
+        }
+
+        // Extend the run to the left and right
+        let mut x0 = x;
+        while x0 > 0 && filled[x0 - 1 + y * w] == 0 && matches(x0 - 1, y) {
+            x0 -= 1;
+        }
+        let mut x1 = x;
+        while x1 + 1 < w && filled[x1 + 1 + y * w] == 0 && matches(x1 + 1, y) {
+            x1 += 1;
+        }
+        for v in filled[(x0 + y * w)..=(x1 + y * w)].iter_mut() {
+            *v = 255;
+        }
+
+        // Queue the start of every matching run in the rows above and below
+        let (lo, hi) = if diagonal {
+            (x0.saturating_sub(1), (x1 + 1).min(w - 1))
+        } else {
+            (x0, x1)
+        };
+        for ny in [y.wrapping_sub(1), y + 1].iter().cloned() {
+            if ny >= h {
+                continue;
+            }
+            let mut in_run = false;
+            for nx in lo..=hi {
+                if filled[nx + ny * w] == 0 && matches(nx, ny) {
+                    if !in_run {
+                        stack.push((nx, ny));
+                        in_run = true;
+                    }
+                } else {
+                    in_run = false;
+                }
+            }
+        }
+    }
+
+    let region = mask.bounding_rect()?;
+    mask.crop(region)
+}
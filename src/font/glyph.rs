@@ -1,4 +1,4 @@
-use crate::{Canvas, Image, Rgba};
+use crate::{Canvas, ColorBlendMode, ColorBlendTransparent, Image, Rect, Rgba};
 use rusttype::GlyphId;
 
 pub struct Glyph {
@@ -50,14 +50,73 @@ impl Glyph {
         }
     }
 
-    pub fn draw(&self, c: &mut Canvas, pos: [i32; 2], color: Rgba) {
-        if color.alpha() == 255 {
-            self.draw_solid(c, pos, color);
-        } else {
-            self.draw_alpha(c, pos, color);
+    /// Bounding rectangle of the pixels drawn at the position, or None if the glyph is empty.
+    pub fn bounding_rect(&self, pos: [i32; 2]) -> Option<Rect> {
+        let [mut x, mut y] = pos;
+        let mut min = [i32::MAX, i32::MAX];
+        let mut max = [i32::MIN, i32::MIN];
+        for i in self.inst.iter() {
+            let n = match i {
+                GlyphInst::NextRow => {
+                    y += 1;
+                    x = pos[0];
+                    continue;
+                }
+                GlyphInst::NextNRow(n) => {
+                    y += (*n) as i32;
+                    x = pos[0];
+                    continue;
+                }
+                GlyphInst::XOffset(n) => {
+                    x += (*n) as i32;
+                    continue;
+                }
+                GlyphInst::Blend(_) => 1,
+                GlyphInst::Solid(n) => (*n) as i32,
+            };
+            if n > 0 {
+                min = [min[0].min(x), min[1].min(y)];
+                max = [max[0].max(x + n), max[1].max(y + 1)];
+            }
+            x += n;
+        }
+        if min[0] >= max[0] {
+            return None;
         }
+        let dim = [(max[0] - min[0]) as u32, (max[1] - min[1]) as u32];
+        Some(Rect::new(min, dim))
+    }
+
+    pub fn draw(&self, c: &mut Canvas, pos: [i32; 2], color: Rgba) {
+        self.draw_using(ColorBlendTransparent, c, pos, color);
+    }
+    pub fn draw_using<Mode: ColorBlendMode>(
+        &self,
+        mode: Mode,
+        c: &mut Canvas,
+        pos: [i32; 2],
+        color: Rgba,
+    ) {
+        let rect = match self.bounding_rect(pos) {
+            Some(rect) => rect,
+            None => return,
+        };
+        // Note: Records the damage once for the glyph instead of for every pixel
+        c.with_damage_rect(rect, |c| {
+            if color.alpha() == 255 {
+                self.draw_solid(mode, c, pos, color);
+            } else {
+                self.draw_alpha(mode, c, pos, color);
+            }
+        });
     }
-    fn draw_solid(&self, c: &mut Canvas, pos: [i32; 2], color: Rgba) {
+    fn draw_solid<Mode: ColorBlendMode>(
+        &self,
+        mode: Mode,
+        c: &mut Canvas,
+        pos: [i32; 2],
+        color: Rgba,
+    ) {
         let [mut x, mut y] = pos;
         for i in self.inst.iter() {
             match i {
@@ -75,7 +134,7 @@ impl Glyph {
                 GlyphInst::Blend(alpha) => {
                     let mut alpha_color = color;
                     alpha_color.0[3] = *alpha;
-                    c.try_blend_color([x, y], alpha_color);
+                    c.try_blend_color_using(mode, [x, y], alpha_color);
                     x += 1;
                 }
                 GlyphInst::Solid(n) => {
@@ -87,7 +146,13 @@ impl Glyph {
             }
         }
     }
-    fn draw_alpha(&self, c: &mut Canvas, pos: [i32; 2], color: Rgba) {
+    fn draw_alpha<Mode: ColorBlendMode>(
+        &self,
+        mode: Mode,
+        c: &mut Canvas,
+        pos: [i32; 2],
+        color: Rgba,
+    ) {
         let base_alpha = color.alpha() as u32;
         let [mut x, mut y] = pos;
         for i in self.inst.iter() {
@@ -107,12 +172,12 @@ impl Glyph {
                     let rel_alpha = ((*alpha) as u32) * base_alpha / 255;
                     let mut alpha_color = color;
                     alpha_color.0[3] = rel_alpha as u8;
-                    c.try_blend_color([x, y], alpha_color);
+                    c.try_blend_color_using(mode, [x, y], alpha_color);
                     x += 1;
                 }
                 GlyphInst::Solid(n) => {
                     for _ in 0..(*n) {
-                        c.try_blend_color([x, y], color);
+                        c.try_blend_color_using(mode, [x, y], color);
                         x += 1;
                     }
                 }
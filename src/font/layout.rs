@@ -1,6 +1,6 @@
 use crate::font::align::{HorzAlign, ScriptPosition};
 use crate::font::*;
-use crate::{Canvas, Rgba};
+use crate::{Canvas, ColorBlendMode, ColorBlendTransparent, Rect, Rgba};
 use std::cmp::max;
 
 pub struct TextLayout<'fontcache> {
@@ -14,7 +14,26 @@ impl<'fontcache> TextLayout<'fontcache> {
         self.min_dim
     }
     pub fn render(&self, pos: [i32; 2], width: u32, c: &mut Canvas) {
-        render(c, pos, width, self);
+        render(ColorBlendTransparent, c, pos, width, self);
+    }
+    pub fn render_using<Mode: ColorBlendMode>(
+        &self,
+        mode: Mode,
+        pos: [i32; 2],
+        width: u32,
+        c: &mut Canvas,
+    ) {
+        render(mode, c, pos, width, self);
+    }
+    /// Bounding rectangle of the pixels drawn by `render`, or None if nothing is drawn.
+    pub fn bounding_rect(&self, pos: [i32; 2], width: u32) -> Option<Rect> {
+        let mut result: Option<Rect> = None;
+        for_each_glyph(self, pos, width, |g, draw_pos, _| {
+            if let Some(rect) = g.bounding_rect(draw_pos) {
+                result = Some(result.map_or(rect, |r| r.union(rect)));
+            }
+        });
+        result
     }
 }
 
@@ -75,6 +94,16 @@ impl<'fontcache> LinkLayout<'fontcache> {
         &self.bounding_boxes
     }
     pub fn render(&self, pos: [i32; 2], final_width: u32, link_color: Rgba, c: &mut Canvas) {
+        self.render_using(ColorBlendTransparent, pos, final_width, link_color, c);
+    }
+    pub fn render_using<Mode: ColorBlendMode>(
+        &self,
+        mode: Mode,
+        pos: [i32; 2],
+        final_width: u32,
+        link_color: Rgba,
+        c: &mut Canvas,
+    ) {
         let center_extra = (final_width as i32) - (self.layout_width as i32);
         // let center_extra = max(center_extra, 0);
 
@@ -86,7 +115,7 @@ impl<'fontcache> LinkLayout<'fontcache> {
                     let glyphs = &self.glyphs[(glyph_range[0] as usize)..(glyph_range[1] as usize)];
                     for (g, rel_pos, ..) in glyphs.iter() {
                         let draw_pos = [line_offset + rel_pos[0], pos[1] + rel_pos[1]];
-                        g.draw(c, draw_pos, link_color);
+                        g.draw_using(mode, c, draw_pos, link_color);
                     }
                 }
                 TextLayoutCmd::LineSettings {
@@ -386,11 +415,24 @@ impl<'fontcache> TextLayoutBuilder<'fontcache> {
     }
 }
 
-fn render<'fontcache>(
+fn render<'fontcache, Mode: ColorBlendMode>(
+    mode: Mode,
     c: &mut Canvas,
     pos: [i32; 2],
     final_width: u32,
     layout: &TextLayout<'fontcache>,
+) {
+    for_each_glyph(layout, pos, final_width, |g, draw_pos, color| {
+        g.draw_using(mode, c, draw_pos, color);
+    });
+}
+
+/// Calls the function with every glyph of the layout, its position and its color.
+fn for_each_glyph<'fontcache, F: FnMut(&'fontcache Glyph, [i32; 2], Rgba)>(
+    layout: &TextLayout<'fontcache>,
+    pos: [i32; 2],
+    final_width: u32,
+    mut f: F,
 ) {
     let center_extra = (final_width as i32) - (layout.layout_width as i32);
     // let center_extra = max(center_extra, 0);
@@ -403,7 +445,7 @@ fn render<'fontcache>(
                 let glyphs = &layout.glyphs[(glyph_range[0] as usize)..(glyph_range[1] as usize)];
                 for (g, rel_pos, actual_color) in glyphs.iter() {
                     let draw_pos = [line_offset + rel_pos[0], pos[1] + rel_pos[1]];
-                    g.draw(c, draw_pos, *actual_color);
+                    f(g, draw_pos, *actual_color);
                 }
             }
             TextLayoutCmd::LineSettings {
@@ -10,11 +10,12 @@ pub(crate) mod svg_font;
 pub(crate) mod ttf_font;
 
 use self::glyph::Glyph;
+use self::layout::TextLayout;
 use self::pinned_cache::{CacheEditor, PinnedCache};
 use self::svg_font::SvgFont;
 use self::ttf_font::TtfFont;
 use crate::errors::FontLoadError;
-use crate::{Canvas, Rgba};
+use crate::{Canvas, ColorBlendMode, ColorBlendTransparent, Rect, Rgba};
 use std::collections::HashMap;
 
 const FONT_CACHE_PAGE_SIZE: usize = 512;
@@ -98,19 +99,50 @@ impl<'a> Font<'a> {
         width: Option<u32>,
         c: &mut Canvas,
     ) -> [u32; 2] {
+        self.render_using(ColorBlendTransparent, font_size, color, text, pos, width, c)
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn render_using<Mode: ColorBlendMode>(
+        &mut self,
+        mode: Mode,
+        font_size: u32,
+        color: Rgba,
+        text: &str,
+        pos: [i32; 2],
+        width: Option<u32>,
+        c: &mut Canvas,
+    ) -> [u32; 2] {
+        let w = width.unwrap_or(std::u32::MAX / 4);
+        let layout = self.layout(font_size, color, text, w);
+        layout.render_using(mode, pos, w, c);
+        layout.required_dim()
+    }
+
+    /// Bounding rectangle of the pixels drawn by `render`, or None if nothing is drawn.
+    pub fn render_bounds(
+        &mut self,
+        font_size: u32,
+        text: &str,
+        pos: [i32; 2],
+        width: Option<u32>,
+    ) -> Option<Rect> {
+        let w = width.unwrap_or(std::u32::MAX / 4);
+        let layout = self.layout(font_size, Rgba([0, 0, 0, 255]), text, w);
+        layout.bounding_rect(pos, w)
+    }
+
+    fn layout(&mut self, font_size: u32, color: Rgba, text: &str, w: u32) -> TextLayout<'a> {
         let font = unsafe {
             let lifetime_ptr = self as *mut Font<'a>;
             let static_ptr: *mut Font<'static> = std::mem::transmute(lifetime_ptr);
             &mut (*static_ptr)
         };
-        let w = width.unwrap_or(std::u32::MAX / 4);
         let mut b = crate::font::layout::TextLayoutBuilder::new(Some(w));
         b.set_font_size(font, font_size);
         b.set_color(color);
         b.add_text(font, text);
-        let layout = b.build();
-        layout.render(pos, w, c);
-        layout.required_dim()
+        b.build()
     }
 }
 
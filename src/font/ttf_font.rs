@@ -1,6 +1,8 @@
 use crate::font::glyph::Glyph;
-use crate::{Image, Rgba};
-use rusttype::{point, Font, FontCollection, Scale};
+use crate::path::Path;
+use crate::raster::{FillRule, Rasterizer};
+use crate::{Image, Rect, Rgba};
+use rusttype::{point, Font, FontCollection, Scale, Segment};
 use std::cmp::max;
 
 pub struct TtfFont {
@@ -58,9 +60,42 @@ impl TtfFont {
             // Draw the glyph onto an image
             let (width, height) = (bb.width() as u32, bb.height() as u32);
             let mut img = Image::new([width, height]);
-            pos_glyph.draw(|x, y, v| {
-                img.set([x, y], Rgba::from_f32([0., 0., 0., v]));
-            });
+            if let Some(contours) = pos_glyph.unpositioned().shape() {
+                // Note: The glyph shape uses font coordinates (y-axis pointing up)
+                let origin = [bb.min.x as f32, bb.min.y as f32 - v_metrics.ascent];
+                let to_img = |p: rusttype::Point<f32>| [p.x - origin[0], -p.y - origin[1]];
+                let mut path = Path::new();
+                for contour in contours.iter() {
+                    for (idx, segment) in contour.segments.iter().enumerate() {
+                        match segment {
+                            Segment::Line(l) => {
+                                if idx == 0 {
+                                    path.move_to(to_img(l.p[0]));
+                                }
+                                path.line_to(to_img(l.p[1]));
+                            }
+                            Segment::Curve(c) => {
+                                if idx == 0 {
+                                    path.move_to(to_img(c.p[0]));
+                                }
+                                path.quad_to(to_img(c.p[1]), to_img(c.p[2]));
+                            }
+                        }
+                    }
+                    path.close();
+                }
+                let mut r = Rasterizer::new(Rect::new([0, 0], [width, height]));
+                for contour in path.flatten() {
+                    r.add_contour(&contour.pts);
+                }
+                r.for_each_coverage(FillRule::NonZero, |pos, alpha| {
+                    img.set(pos, Rgba([0, 0, 0, alpha]));
+                });
+            } else {
+                pos_glyph.draw(|x, y, v| {
+                    img.set([x, y], Rgba::from_f32([0., 0., 0., v]));
+                });
+            }
 
             // Note: Some letters have negative offsets - ignore this for now
             let x_offset = max(bb.min.x, 0) as u32;
@@ -0,0 +1,56 @@
+// Small helpers for working with [f32; 2] points/vectors
+
+pub(crate) fn add(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] + b[0], a[1] + b[1]]
+}
+
+pub(crate) fn sub(a: [f32; 2], b: [f32; 2]) -> [f32; 2] {
+    [a[0] - b[0], a[1] - b[1]]
+}
+
+pub(crate) fn mul(a: [f32; 2], v: f32) -> [f32; 2] {
+    [a[0] * v, a[1] * v]
+}
+
+pub(crate) fn dot(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[0] + a[1] * b[1]
+}
+
+pub(crate) fn cross(a: [f32; 2], b: [f32; 2]) -> f32 {
+    a[0] * b[1] - a[1] * b[0]
+}
+
+pub(crate) fn length(a: [f32; 2]) -> f32 {
+    dot(a, a).sqrt()
+}
+
+pub(crate) fn normalize(a: [f32; 2]) -> [f32; 2] {
+    let len = length(a);
+    if len > 0.0 {
+        mul(a, 1.0 / len)
+    } else {
+        [0.0, 0.0]
+    }
+}
+
+/// Vector rotated by 90 degrees.
+pub(crate) fn perp(a: [f32; 2]) -> [f32; 2] {
+    [-a[1], a[0]]
+}
+
+pub(crate) fn is_finite(a: [f32; 2]) -> bool {
+    a[0].is_finite() && a[1].is_finite()
+}
+
+/// Twice the signed area of the polygon, the sign depends on the orientation of the points.
+pub(crate) fn signed_area(pts: &[[f32; 2]]) -> f32 {
+    let mut sum = 0.0;
+    if let Some(last) = pts.last() {
+        let mut prev = *last;
+        for p in pts.iter().cloned() {
+            sum += cross(prev, p);
+            prev = p;
+        }
+    }
+    sum
+}
@@ -0,0 +1,216 @@
+use crate::rgba::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Geometry of a gradient, it maps every point to an offset along the color stops.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GradientShape {
+    /// Offset 0.0 at `start` and 1.0 at `end`, constant along the perpendicular lines.
+    Linear { start: [f32; 2], end: [f32; 2] },
+    /// Offset 0.0 at the center and 1.0 at the circle with the specified radius.
+    Radial { center: [f32; 2], radius: f32 },
+    /// Offset increases clockwise around the center, starting with 0.0 at `start_angle`
+    /// (radians, measured from the positive x-axis) and reaching 1.0 after a full turn.
+    Conic { center: [f32; 2], start_angle: f32 },
+}
+
+/// Determines the color of points with an offset outside of the range 0.0 to 1.0.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum SpreadMode {
+    /// Uses the color of the first or last stop.
+    Pad,
+    /// Starts again from the first stop.
+    Repeat,
+    /// Alternates between running forward and backward through the stops.
+    Reflect,
+}
+
+/// Color space used to interpolate between color stops.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ColorInterpolation {
+    /// Interpolates the sRGB values directly (like CSS and most browsers do).
+    Srgb,
+    /// Interpolates in linear light, which avoids dark bands between saturated colors.
+    LinearRgb,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ColorStop {
+    pub offset: f32,
+    pub color: Rgba,
+}
+
+/// Linear, radial or conic color gradient. Positions use the coordinate space of the image that
+/// is drawn on, pixels are sampled at their center (`x + 0.5`, `y + 0.5`).
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct Gradient {
+    shape: GradientShape,
+    stops: Vec<ColorStop>,
+    spread: SpreadMode,
+    interpolation: ColorInterpolation,
+}
+
+impl Gradient {
+    pub fn new(shape: GradientShape) -> Self {
+        Self {
+            shape,
+            stops: Vec::new(),
+            spread: SpreadMode::Pad,
+            interpolation: ColorInterpolation::Srgb,
+        }
+    }
+
+    pub fn linear(start: [f32; 2], end: [f32; 2]) -> Self {
+        Self::new(GradientShape::Linear { start, end })
+    }
+
+    pub fn radial(center: [f32; 2], radius: f32) -> Self {
+        Self::new(GradientShape::Radial { center, radius })
+    }
+
+    pub fn conic(center: [f32; 2], start_angle: f32) -> Self {
+        Self::new(GradientShape::Conic {
+            center,
+            start_angle,
+        })
+    }
+
+    /// Adds a color stop. Stops are kept sorted by their offset, a stop with the same offset as
+    /// an existing stop is placed after it (which results in a hard transition).
+    pub fn with_stop(mut self, offset: f32, color: Rgba) -> Self {
+        let idx = self
+            .stops
+            .iter()
+            .position(|s| s.offset > offset)
+            .unwrap_or(self.stops.len());
+        self.stops.insert(idx, ColorStop { offset, color });
+        self
+    }
+
+    pub fn with_spread(mut self, spread: SpreadMode) -> Self {
+        self.spread = spread;
+        self
+    }
+
+    pub fn with_interpolation(mut self, interpolation: ColorInterpolation) -> Self {
+        self.interpolation = interpolation;
+        self
+    }
+
+    pub fn shape(&self) -> GradientShape {
+        self.shape
+    }
+    pub fn stops(&self) -> &[ColorStop] {
+        &self.stops
+    }
+    pub fn spread(&self) -> SpreadMode {
+        self.spread
+    }
+    pub fn interpolation(&self) -> ColorInterpolation {
+        self.interpolation
+    }
+
+    /// True if every color of the gradient is fully opaque.
+    pub fn is_opaque(&self) -> bool {
+        !self.stops.is_empty() && self.stops.iter().all(|s| s.color.alpha() == 255)
+    }
+
+    /// Color of the gradient at the specified position.
+    pub fn color_at(&self, pos: [f32; 2]) -> Rgba {
+        let t = self.offset_at(pos);
+        self.color_at_offset(t)
+    }
+
+    /// Color of the pixel at the specified position.
+    pub(crate) fn pixel_color(&self, pos: [i32; 2]) -> Rgba {
+        self.color_at([pos[0] as f32 + 0.5, pos[1] as f32 + 0.5])
+    }
+
+    fn offset_at(&self, pos: [f32; 2]) -> f32 {
+        let t = match self.shape {
+            GradientShape::Linear { start, end } => {
+                let d = [end[0] - start[0], end[1] - start[1]];
+                let len2 = d[0] * d[0] + d[1] * d[1];
+                ((pos[0] - start[0]) * d[0] + (pos[1] - start[1]) * d[1]) / len2
+            }
+            GradientShape::Radial { center, radius } => {
+                let d = [pos[0] - center[0], pos[1] - center[1]];
+                (d[0] * d[0] + d[1] * d[1]).sqrt() / radius
+            }
+            GradientShape::Conic {
+                center,
+                start_angle,
+            } => {
+                let a = (pos[1] - center[1]).atan2(pos[0] - center[0]) - start_angle;
+                (a / (2.0 * PI)).rem_euclid(1.0)
+            }
+        };
+        if !t.is_finite() {
+            // Note: Degenerate geometry (zero length or radius)
+            return 0.0;
+        }
+        match self.spread {
+            SpreadMode::Pad => t.clamp(0.0, 1.0),
+            SpreadMode::Repeat => t - t.floor(),
+            SpreadMode::Reflect => {
+                let v = t.rem_euclid(2.0);
+                if v > 1.0 {
+                    2.0 - v
+                } else {
+                    v
+                }
+            }
+        }
+    }
+
+    fn color_at_offset(&self, t: f32) -> Rgba {
+        let (first, last) = match (self.stops.first(), self.stops.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return Rgba([0, 0, 0, 0]),
+        };
+        if t <= first.offset {
+            return first.color;
+        }
+        if t >= last.offset {
+            return last.color;
+        }
+        for pair in self.stops.windows(2) {
+            let (a, b) = (&pair[0], &pair[1]);
+            if t < b.offset {
+                let f = (t - a.offset) / (b.offset - a.offset);
+                return self.mix(a.color, b.color, f);
+            }
+        }
+        last.color
+    }
+
+    fn mix(&self, a: Rgba, b: Rgba, f: f32) -> Rgba {
+        let to_f32 = |c: Rgba| match self.interpolation {
+            ColorInterpolation::Srgb => c.rgba_f32(),
+            ColorInterpolation::LinearRgb => c.srgb_to_linear_f32(),
+        };
+        // Note: Interpolates premultiplied colors, otherwise fading to a transparent stop
+        // would also fade towards its (invisible) color
+        let (a, b) = (to_f32(a), to_f32(b));
+        let alpha = a[3] + (b[3] - a[3]) * f;
+        if alpha <= 0.0 {
+            return Rgba([0, 0, 0, 0]);
+        }
+        let mut c = [0.0; 4];
+        for i in 0..3 {
+            let v = a[i] * a[3] + (b[i] * b[3] - a[i] * a[3]) * f;
+            c[i] = v / alpha;
+        }
+        c[3] = alpha;
+        match self.interpolation {
+            ColorInterpolation::Srgb => Rgba::from_f32(c),
+            ColorInterpolation::LinearRgb => Rgba::from_linear_f32(c),
+        }
+    }
+}
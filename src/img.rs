@@ -2,6 +2,7 @@ use crate::blend::{ColorBlendMode, ColorBlendTransparent};
 use crate::canvas::Canvas;
 use crate::errors::ImageLoadError;
 use crate::idx::Indexable2D;
+use crate::premul::PremulRgba;
 use crate::resize_filter::ResizeFilter;
 use crate::rgba::Rgba;
 use crate::rows::{RowsIter, RowsMutIter};
@@ -85,6 +86,32 @@ impl Image {
         }
     }
 
+    pub fn from_premul_buffer(dim: [u32; 2], buffer: &[PremulRgba]) -> Image {
+        let [width, height] = dim;
+        let len = (width as usize) * (height as usize);
+        if buffer.len() != len {
+            panic!(
+                "Dimensions do not match: {}x{}={}, buffer length is {}",
+                width,
+                height,
+                len,
+                buffer.len()
+            );
+        }
+        Image {
+            dim: [width, height],
+            contents: buffer.iter().map(|c| c.to_rgba()).collect(),
+        }
+    }
+
+    /// Copy of the pixels with premultiplied alpha, row by row.
+    pub fn to_premul_buffer(&self) -> Vec<PremulRgba> {
+        self.contents
+            .iter()
+            .map(|c| PremulRgba::from_rgba(*c))
+            .collect()
+    }
+
     pub fn open(file: impl AsRef<std::path::Path>) -> Result<Image, ImageLoadError> {
         Ok(image::open(file)?.to_rgba8().into())
     }
@@ -0,0 +1,95 @@
+use crate::affine::Affine;
+use crate::blend::{ImageBlendTinted, ImageBlendTransparent, MixBlendMode};
+use crate::canvas::Canvas;
+use crate::img::Image;
+use crate::rgba::Rgba;
+
+/// Offscreen image that covers the region of the base canvas.
+struct Layer {
+    img: Image,
+    pos: [i32; 2],
+    opacity: f32,
+    mode: MixBlendMode,
+}
+
+/// Stack of offscreen layers on top of a canvas. Drawing goes to the top layer, which is
+/// composited onto the layer below it (or the base canvas) when it is popped, using its opacity
+/// and blend mode. This allows grouped transparency, for example drawing a shape and its label
+/// at 50% opacity as one unit. Layers that are still on the stack when it is dropped are popped.
+pub struct LayerStack<'c, 'a> {
+    base: &'c mut Canvas<'a>,
+    layers: Vec<Layer>,
+}
+
+impl<'c, 'a> LayerStack<'c, 'a> {
+    pub fn new(base: &'c mut Canvas<'a>) -> Self {
+        Self {
+            base,
+            layers: Vec::new(),
+        }
+    }
+
+    /// Pushes a new transparent layer, the opacity ranges from 0.0 to 1.0.
+    pub fn push_layer(&mut self, opacity: f32, mode: MixBlendMode) {
+        self.layers.push(Layer {
+            img: Image::new(self.base.dim()),
+            pos: self.base.pos(),
+            opacity,
+            mode,
+        });
+    }
+
+    /// Composites the top layer onto the layer below it. Returns false if there is no layer.
+    pub fn pop_layer(&mut self) -> bool {
+        let layer = match self.layers.pop() {
+            Some(layer) => layer,
+            None => return false,
+        };
+        let white = Rgba([255, 255, 255, 255]);
+        let mut target = self.canvas();
+        // Note: The layer image uses the coordinate space of the base image
+        target.set_transform(Affine::identity());
+        if layer.mode == MixBlendMode::Normal {
+            let mode = ImageBlendTinted::new(ImageBlendTransparent, white);
+            target.draw_image_using(mode.with_opacity(layer.opacity), &layer.img, layer.pos);
+        } else {
+            let mode = ImageBlendTinted::new(layer.mode, white);
+            target.draw_image_using(mode.with_opacity(layer.opacity), &layer.img, layer.pos);
+        }
+        true
+    }
+
+    /// Number of layers on the stack, not counting the base canvas.
+    pub fn depth(&self) -> usize {
+        self.layers.len()
+    }
+
+    /// Canvas of the top layer (or the base canvas if there is no layer). It uses the coordinate
+    /// space (and transformation) of the base canvas.
+    pub fn canvas(&mut self) -> Canvas<'_> {
+        let transform = self.base.transform();
+        match self.layers.last_mut() {
+            Some(layer) => {
+                let dim = layer.img.dim();
+                Canvas::new(&mut layer.img, 0, layer.pos, dim).with_transform(transform)
+            }
+            None => self.base.reborrow(),
+        }
+    }
+}
+
+impl<'c, 'a> Drop for LayerStack<'c, 'a> {
+    fn drop(&mut self) {
+        while self.pop_layer() {}
+    }
+}
+
+impl<'a> Canvas<'a> {
+    /// Draws into a temporary layer, which is composited onto this canvas using the opacity and
+    /// blend mode once the closure returns.
+    pub fn draw_layer<F: FnOnce(&mut Canvas)>(&mut self, opacity: f32, mode: MixBlendMode, f: F) {
+        let mut stack = LayerStack::new(self);
+        stack.push_layer(opacity, mode);
+        f(&mut stack.canvas());
+    }
+}
@@ -1,27 +1,60 @@
+mod affine;
 mod blend;
 mod canvas;
+mod damage;
+mod display_list;
 mod errors;
+mod flood;
 mod font;
+mod geom;
+mod gradient;
 mod hsl_color;
 mod hsv_color;
 mod idx;
 mod img;
+mod layer;
+mod line;
+mod mask;
 mod minmaxf32;
+mod nine_slice;
+mod paint;
+#[cfg(feature = "rayon")]
+mod par;
+mod path;
+mod pixels;
+mod premul;
+mod raster;
 mod rect;
 mod resize_filter;
 mod rgba;
 mod rows;
+mod sample;
+mod shapes;
+mod srgb_lut;
+mod stroke;
 mod sub_img_params;
 
-pub use crate::blend::{ColorAlphaBlendMode, ColorBlendMode, ImageBlendMode};
+pub use crate::affine::Affine;
 pub use crate::blend::{
-    ColorAlphaBlendOpaque, ColorAlphaBlendOverwrite, ColorAlphaBlendTransparent,
-    SolidColorAlphaBlendOverwrite,
+    ColorAlphaBlendMode, ColorBlendMode, ImageBlendMode, MixBlendMode, PorterDuff,
+};
+pub use crate::blend::{
+    ColorAlphaBlendLinear, ColorAlphaBlendOpaque, ColorAlphaBlendOverwrite,
+    ColorAlphaBlendTransparent, ColorAlphaBlendTransparentF32, SolidColorAlphaBlendOverwrite,
+};
+pub use crate::blend::{
+    ColorBlendLinear, ColorBlendOpaque, ColorBlendOverwrite, ColorBlendTransparent,
+    ColorBlendTransparentF32,
+};
+pub use crate::blend::{
+    ImageBlendLinear, ImageBlendOpaque, ImageBlendOverwrite, ImageBlendTinted,
+    ImageBlendTransparent, ImageBlendTransparentF32,
 };
-pub use crate::blend::{ColorBlendOpaque, ColorBlendOverwrite, ColorBlendTransparent};
-pub use crate::blend::{ImageBlendOpaque, ImageBlendOverwrite, ImageBlendTransparent};
 pub use crate::canvas::Canvas;
-pub use crate::errors::{FontLoadError, ImageLoadError, RgbaParseError};
+pub use crate::damage::DamageTracker;
+pub use crate::display_list::{BlendMode, DisplayList, DrawCommand};
+pub use crate::errors::{FontLoadError, ImageLoadError, NinePatchError, RgbaParseError};
+pub use crate::flood::{Connectivity, FloodFill};
 pub use crate::font::align::{HorzAlign, ScriptPosition, VertAlign};
 pub use crate::font::glyph::{Glyph, GlyphInst};
 pub use crate::font::layout::{
@@ -30,14 +63,25 @@ pub use crate::font::layout::{
 pub use crate::font::svg_font::SvgFont;
 pub use crate::font::ttf_font::TtfFont;
 pub use crate::font::{Font, FontCache, StaticFontCache};
+pub use crate::gradient::{ColorInterpolation, ColorStop, Gradient, GradientShape, SpreadMode};
 pub use crate::hsl_color::Hsl;
 pub use crate::hsv_color::Hsv;
 pub use crate::idx::Indexable2D;
 pub use crate::img::Image;
+pub use crate::layer::LayerStack;
+pub use crate::line::LineMode;
+pub use crate::mask::Mask;
+pub use crate::nine_slice::{NineSlice, SliceMode};
+pub use crate::paint::{ImagePattern, Paint, PatternRepeat};
+pub use crate::path::Path;
+pub use crate::premul::PremulRgba;
+pub use crate::raster::FillRule;
 pub use crate::rect::Rect;
 pub use crate::resize_filter::ResizeFilter;
 pub use crate::rgba::Rgba;
 pub use crate::rows::{RowsIter, RowsMutIter};
+pub use crate::sample::SampleFilter;
+pub use crate::stroke::{LineCap, LineJoin, StrokeStyle};
 pub use crate::sub_img_params::{
     MarginValue, SpacingValue, SubImageBuilder, SubImageParams, SubImageParamsIter,
 };
@@ -0,0 +1,223 @@
+use crate::geom::is_finite;
+use crate::rect::Rect;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Rasterization strategy used when drawing one pixel wide lines.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum LineMode {
+    /// Bresenham line, every pixel is either fully drawn or not drawn at all.
+    Aliased,
+    /// Xiaolin Wu line, pixels along the line are blended based on their coverage.
+    AntiAliased,
+}
+
+/// Clips the segment against the specified bounds (Liang-Barsky). Returns None if the segment is
+/// entirely outside of the bounds.
+pub(crate) fn clip_segment(
+    p0: [f32; 2],
+    p1: [f32; 2],
+    min: [f32; 2],
+    max: [f32; 2],
+) -> Option<([f32; 2], [f32; 2])> {
+    // Note: Computed using f64, otherwise very long lines lose too much precision
+    let p0 = [p0[0] as f64, p0[1] as f64];
+    let d = [p1[0] as f64 - p0[0], p1[1] as f64 - p0[1]];
+    let mut t0 = 0.0f64;
+    let mut t1 = 1.0f64;
+    for axis in 0..2 {
+        let lo = p0[axis] - min[axis] as f64;
+        let hi = max[axis] as f64 - p0[axis];
+        for &(p, q) in [(-d[axis], lo), (d[axis], hi)].iter() {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let r = q / p;
+                if p < 0.0 {
+                    if r > t1 {
+                        return None;
+                    } else if r > t0 {
+                        t0 = r;
+                    }
+                } else if r < t0 {
+                    return None;
+                } else if r < t1 {
+                    t1 = r;
+                }
+            }
+        }
+    }
+    let a = [(p0[0] + t0 * d[0]) as f32, (p0[1] + t0 * d[1]) as f32];
+    let b = [(p0[0] + t1 * d[0]) as f32, (p0[1] + t1 * d[1]) as f32];
+    Some((a, b))
+}
+
+/// Calls `plot` for every pixel of the Bresenham line between the pixels containing `p0` and
+/// `p1`. Only the steps that fall within the bounds are visited.
+pub(crate) fn plot_aliased_line(
+    p0: [f32; 2],
+    p1: [f32; 2],
+    bounds: Rect,
+    mut plot: impl FnMut([i32; 2]),
+) {
+    if bounds.is_empty() || !is_finite(p0) || !is_finite(p1) {
+        return;
+    }
+    let a = [to_pixel(p0[0]), to_pixel(p0[1])];
+    let b = [to_pixel(p1[0]), to_pixel(p1[1])];
+    let d = [b[0] - a[0], b[1] - a[1]];
+    let s = [d[0].signum(), d[1].signum()];
+    let abs_d = [d[0].abs(), d[1].abs()];
+
+    // The major axis advances by one on every step, the minor axis by the rounded slope
+    let (major, minor) = if abs_d[0] >= abs_d[1] { (0, 1) } else { (1, 0) };
+    let steps = abs_d[major];
+
+    // Limit the steps to those where the major axis lies within the bounds
+    let lo = bounds.pos[major] as i64;
+    let hi = lo + (bounds.dim[major] as i64) - 1;
+    let (k0, k1) = if s[major] >= 0 {
+        (lo - a[major], hi - a[major])
+    } else {
+        (a[major] - hi, a[major] - lo)
+    };
+    let k0 = k0.max(0);
+    let k1 = k1.min(steps);
+
+    for k in k0..=k1 {
+        let minor_offset = if steps == 0 {
+            0
+        } else {
+            (2 * k * abs_d[minor] + steps) / (2 * steps)
+        };
+        let mut pos = [0, 0];
+        pos[major] = (a[major] + s[major] * k) as i32;
+        pos[minor] = (a[minor] + s[minor] * minor_offset) as i32;
+        plot(pos);
+    }
+}
+
+/// Calls `plot` with the coverage (0 to 255) of every pixel touched by the anti-aliased line
+/// from `p0` to `p1`. Pixel centers are located at `x + 0.5`, `y + 0.5`.
+pub(crate) fn plot_antialiased_line(
+    p0: [f32; 2],
+    p1: [f32; 2],
+    bounds: Rect,
+    mut plot: impl FnMut([i32; 2], u8),
+) {
+    if bounds.is_empty() || !is_finite(p0) || !is_finite(p1) {
+        return;
+    }
+    // Clip with a small margin so that the end points of the clipped line are never visible
+    let margin = 2.0;
+    let min = [bounds.x() as f32 - margin, bounds.y() as f32 - margin];
+    let max = [
+        bounds.end_x() as f32 + margin,
+        bounds.end_y() as f32 + margin,
+    ];
+    let (p0, p1) = if let Some(clipped) = clip_segment(p0, p1, min, max) {
+        clipped
+    } else {
+        return;
+    };
+
+    // Work in a coordinate space where the pixel centers are on integer values
+    let (mut x0, mut y0) = (p0[0] - 0.5, p0[1] - 0.5);
+    let (mut x1, mut y1) = (p1[0] - 0.5, p1[1] - 0.5);
+    let steep = (y1 - y0).abs() > (x1 - x0).abs();
+    if steep {
+        std::mem::swap(&mut x0, &mut y0);
+        std::mem::swap(&mut x1, &mut y1);
+    }
+    if x0 > x1 {
+        std::mem::swap(&mut x0, &mut x1);
+        std::mem::swap(&mut y0, &mut y1);
+    }
+
+    let mut emit = |major: i32, minor: i32, coverage: f32| {
+        let alpha = (coverage * 255.0 + 0.5) as i32;
+        if alpha > 0 {
+            let alpha = if alpha > 255 { 255 } else { alpha as u8 };
+            if steep {
+                plot([minor, major], alpha);
+            } else {
+                plot([major, minor], alpha);
+            }
+        }
+    };
+
+    let dx = x1 - x0;
+    let gradient = if dx <= f32::EPSILON {
+        1.0
+    } else {
+        (y1 - y0) / dx
+    };
+
+    let x_start = round(x0);
+    let x_end = round(x1);
+    if x_start == x_end {
+        // The whole line lies within a single column
+        let y = 0.5 * (y0 + y1);
+        let weight = dx.clamp(0.0, 1.0);
+        let y_floor = y.floor();
+        emit(
+            x_start as i32,
+            y_floor as i32,
+            (1.0 - (y - y_floor)) * weight,
+        );
+        emit(x_start as i32, y_floor as i32 + 1, (y - y_floor) * weight);
+        return;
+    }
+
+    // First end point
+    let y_end = y0 + gradient * (x_start - x0);
+    let x_gap = 1.0 - fract(x0 + 0.5);
+    let y_floor = y_end.floor();
+    emit(
+        x_start as i32,
+        y_floor as i32,
+        (1.0 - (y_end - y_floor)) * x_gap,
+    );
+    emit(
+        x_start as i32,
+        y_floor as i32 + 1,
+        (y_end - y_floor) * x_gap,
+    );
+    let mut inter_y = y_end + gradient;
+
+    // Second end point
+    let y_end = y1 + gradient * (x_end - x1);
+    let x_gap = fract(x1 + 0.5);
+    let y_floor = y_end.floor();
+    emit(
+        x_end as i32,
+        y_floor as i32,
+        (1.0 - (y_end - y_floor)) * x_gap,
+    );
+    emit(x_end as i32, y_floor as i32 + 1, (y_end - y_floor) * x_gap);
+
+    // Everything in between
+    for x in (x_start as i32 + 1)..(x_end as i32) {
+        let y_floor = inter_y.floor();
+        emit(x, y_floor as i32, 1.0 - (inter_y - y_floor));
+        emit(x, y_floor as i32 + 1, inter_y - y_floor);
+        inter_y += gradient;
+    }
+}
+
+fn to_pixel(v: f32) -> i64 {
+    // Keep the coordinates small enough that the step calculations can not overflow
+    const LIMIT: f32 = (1 << 26) as f32;
+    v.clamp(-LIMIT, LIMIT).floor() as i64
+}
+
+fn round(v: f32) -> f32 {
+    (v + 0.5).floor()
+}
+
+fn fract(v: f32) -> f32 {
+    v - v.floor()
+}
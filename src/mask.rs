@@ -0,0 +1,112 @@
+use crate::rect::Rect;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// 8-bit coverage values for a rectangular region. Positions use the coordinate space of the
+/// image, every position outside of the rectangle has a coverage of 0.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Mask {
+    rect: Rect,
+    data: Vec<u8>,
+}
+
+impl Mask {
+    pub fn new(rect: Rect) -> Self {
+        Self::new_with_value(rect, 0)
+    }
+
+    pub fn new_with_value(rect: Rect, value: u8) -> Self {
+        let len = (rect.width() as usize) * (rect.height() as usize);
+        Self {
+            rect,
+            data: vec![value; len],
+        }
+    }
+
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Coverage values, row by row.
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+    pub fn data_mut(&mut self) -> &mut [u8] {
+        &mut self.data
+    }
+
+    pub fn nth_row(&self, n: u32) -> &[u8] {
+        let w = self.rect.width() as usize;
+        let idx = (n as usize) * w;
+        &self.data[idx..(idx + w)]
+    }
+
+    fn try_index_at(&self, pos: [i32; 2]) -> Option<usize> {
+        let dx = (pos[0] as i64) - (self.rect.x() as i64);
+        let dy = (pos[1] as i64) - (self.rect.y() as i64);
+        let [w, h] = self.rect.dim;
+        if dx >= 0 && dy >= 0 && dx < (w as i64) && dy < (h as i64) {
+            Some((dx as usize) + (dy as usize) * (w as usize))
+        } else {
+            None
+        }
+    }
+
+    pub fn get(&self, pos: [i32; 2]) -> u8 {
+        match self.try_index_at(pos) {
+            Some(idx) => self.data[idx],
+            None => 0,
+        }
+    }
+
+    pub fn try_set(&mut self, pos: [i32; 2], value: u8) -> bool {
+        if let Some(idx) = self.try_index_at(pos) {
+            self.data[idx] = value;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Smallest rectangle that contains every position with a non-zero coverage.
+    pub fn bounding_rect(&self) -> Option<Rect> {
+        let w = self.rect.width() as usize;
+        if w == 0 {
+            return None;
+        }
+        let mut min = [usize::MAX, usize::MAX];
+        let mut max = [0, 0];
+        for (y, row) in self.data.chunks(w).enumerate() {
+            let first = row.iter().position(|v| *v != 0);
+            let last = row.iter().rposition(|v| *v != 0);
+            if let (Some(first), Some(last)) = (first, last) {
+                min = [min[0].min(first), min[1].min(y)];
+                max = [max[0].max(last), max[1].max(y)];
+            }
+        }
+        if min[0] > max[0] {
+            return None;
+        }
+        let [x, y] = self.rect.pos;
+        Some(Rect::new(
+            [x + (min[0] as i32), y + (min[1] as i32)],
+            [(max[0] - min[0] + 1) as u32, (max[1] - min[1] + 1) as u32],
+        ))
+    }
+
+    /// Copy of the part of the mask that overlaps with the rectangle.
+    pub fn crop(&self, rect: Rect) -> Option<Mask> {
+        let rect = self.rect.intersect(rect)?;
+        let mut result = Mask::new(rect);
+        let w = rect.width() as usize;
+        for y in 0..rect.height() {
+            let src = self
+                .try_index_at([rect.x(), rect.y() + (y as i32)])
+                .unwrap();
+            let dst = (y as usize) * w;
+            result.data[dst..(dst + w)].copy_from_slice(&self.data[src..(src + w)]);
+        }
+        Some(result)
+    }
+}
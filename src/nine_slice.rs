@@ -0,0 +1,295 @@
+use crate::affine::Affine;
+use crate::blend::{ImageBlendMode, ImageBlendTransparent};
+use crate::canvas::Canvas;
+use crate::errors::NinePatchError;
+use crate::img::Image;
+use crate::rect::Rect;
+use crate::rgba::Rgba;
+use crate::sample::{SampleEdge, SampleFilter};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Determines how the edges and the center of a `NineSlice` fill the space between the corners.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum SliceMode {
+    /// The slice is scaled to fill the space.
+    Stretch,
+    /// The slice is repeated at its original size, starting at the top-left corner of the space.
+    /// The last repetition is cut off.
+    Tile,
+}
+
+/// Image that is split into nine slices by the border insets (`[top, right, bottom, left]`, like
+/// CSS `border-image-slice`). When drawn into a rectangle the corners keep their size, the edges
+/// and the center fill the remaining space. The corners shrink proportionally if the rectangle is
+/// smaller than the sum of the insets.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq)]
+pub struct NineSlice {
+    img: Image,
+    insets: [u32; 4],
+    padding: Option<[u32; 4]>,
+    edge_mode: SliceMode,
+    center_mode: SliceMode,
+    filter: SampleFilter,
+}
+
+impl NineSlice {
+    /// Insets that exceed the dimensions of the image are reduced.
+    pub fn new(img: Image, insets: [u32; 4]) -> Self {
+        let [top, right, bottom, left] = insets;
+        let [w, h] = img.dim();
+        let left = left.min(w);
+        let right = right.min(w - left);
+        let top = top.min(h);
+        let bottom = bottom.min(h - top);
+        Self {
+            img,
+            insets: [top, right, bottom, left],
+            padding: None,
+            edge_mode: SliceMode::Stretch,
+            center_mode: SliceMode::Stretch,
+            filter: SampleFilter::Bilinear,
+        }
+    }
+
+    /// Parses an Android style nine-patch image (`.9.png`). The image has a 1 pixel wide border
+    /// with opaque black markers, everything else in the border has to be transparent. The markers
+    /// at the top and left determine the stretchable area (which has to be contiguous), the
+    /// optional markers at the bottom and right determine the padding of the content. An axis
+    /// without markers is stretchable in its entirety. The border is removed from the image.
+    pub fn from_nine_patch(img: &Image) -> Result<Self, NinePatchError> {
+        let [w, h] = img.dim();
+        if w < 3 || h < 3 {
+            return Err(NinePatchError::too_small());
+        }
+        let inner = [w - 2, h - 2];
+        let top = marker_range(img, [1, 0], [1, 0], inner[0])?;
+        let left = marker_range(img, [0, 1], [0, 1], inner[1])?;
+        let bottom = marker_range(img, [1, h - 1], [1, 0], inner[0])?;
+        let right = marker_range(img, [w - 1, 1], [0, 1], inner[1])?;
+
+        let insets_of = |horz: Option<[u32; 2]>, vert: Option<[u32; 2]>| {
+            let [x0, x1] = horz.unwrap_or([0, inner[0]]);
+            let [y0, y1] = vert.unwrap_or([0, inner[1]]);
+            [y0, inner[0] - x1, inner[1] - y1, x0]
+        };
+        let mut result = Self::new(img.sub_image([1, 1], inner), insets_of(top, left));
+        if bottom.is_some() || right.is_some() {
+            result.padding = Some(insets_of(bottom.or(top), right.or(left)));
+        }
+        Ok(result)
+    }
+
+    pub fn with_edge_mode(mut self, mode: SliceMode) -> Self {
+        self.edge_mode = mode;
+        self
+    }
+
+    pub fn with_center_mode(mut self, mode: SliceMode) -> Self {
+        self.center_mode = mode;
+        self
+    }
+
+    /// Filter used to sample the slices that are scaled.
+    pub fn with_filter(mut self, filter: SampleFilter) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Padding of the content (`[top, right, bottom, left]`) used by `content_rect`. Nine-patch
+    /// images set it from their content markers.
+    pub fn with_padding(mut self, padding: Option<[u32; 4]>) -> Self {
+        self.padding = padding;
+        self
+    }
+
+    pub fn image(&self) -> &Image {
+        &self.img
+    }
+    pub fn insets(&self) -> [u32; 4] {
+        self.insets
+    }
+    pub fn padding(&self) -> Option<[u32; 4]> {
+        self.padding
+    }
+    pub fn edge_mode(&self) -> SliceMode {
+        self.edge_mode
+    }
+    pub fn center_mode(&self) -> SliceMode {
+        self.center_mode
+    }
+    pub fn filter(&self) -> SampleFilter {
+        self.filter
+    }
+
+    /// Area for the content when drawn into the rectangle, using the padding if set and
+    /// otherwise the insets.
+    pub fn content_rect(&self, dst_rect: Rect) -> Rect {
+        let [top, right, bottom, left] = self.padding.unwrap_or(self.insets);
+        let width = dst_rect.width().saturating_sub(left + right);
+        let height = dst_rect.height().saturating_sub(top + bottom);
+        Rect::new(
+            [dst_rect.x() + (left as i32), dst_rect.y() + (top as i32)],
+            [width, height],
+        )
+    }
+}
+
+/// Range of the contiguous black markers along one side of a nine-patch border, relative to the
+/// inner image.
+fn marker_range(
+    img: &Image,
+    start: [u32; 2],
+    step: [u32; 2],
+    len: u32,
+) -> Result<Option<[u32; 2]>, NinePatchError> {
+    let mut range: Option<[u32; 2]> = None;
+    for i in 0..len {
+        let pos = [start[0] + step[0] * i, start[1] + step[1] * i];
+        let c = img.get(pos);
+        if c == Rgba([0, 0, 0, 255]) {
+            match &mut range {
+                Some(r) if r[1] == i => r[1] = i + 1,
+                Some(_) => return Err(NinePatchError::split_stretch_area()),
+                None => range = Some([i, i + 1]),
+            }
+        } else if c.alpha() != 0 {
+            return Err(NinePatchError::invalid_marker(pos));
+        }
+    }
+    Ok(range)
+}
+
+/// Splits the source and destination length into the three parts of a slice axis, as
+/// `(src_start, src_len, dst_start, dst_len)`.
+fn split_axis(
+    src_len: u32,
+    insets: [u32; 2],
+    dst_start: i32,
+    dst_len: u32,
+) -> [(u32, u32, i32, u32); 3] {
+    let [start, end] = insets;
+    let sum = start + end;
+    let (dst_a, dst_b) = if sum > dst_len {
+        let a = ((start as u64) * (dst_len as u64) + (sum as u64) / 2) / (sum as u64);
+        (a as u32, dst_len - (a as u32))
+    } else {
+        (start, end)
+    };
+    let center = src_len - sum;
+    let dst_center = dst_len - dst_a - dst_b;
+    [
+        (0, start, dst_start, dst_a),
+        (start, center, dst_start + (dst_a as i32), dst_center),
+        (
+            start + center,
+            end,
+            dst_start + ((dst_a + dst_center) as i32),
+            dst_b,
+        ),
+    ]
+}
+
+impl<'a> Canvas<'a> {
+    pub fn draw_nine_slice(&mut self, slice: &NineSlice, dst_rect: Rect) {
+        self.draw_nine_slice_using(ImageBlendTransparent, slice, dst_rect)
+    }
+
+    /// Draws the nine-slice image so that it fills the rectangle.
+    pub fn draw_nine_slice_using<Mode: ImageBlendMode>(
+        &mut self,
+        mode: Mode,
+        slice: &NineSlice,
+        dst_rect: Rect,
+    ) {
+        let [top, right, bottom, left] = slice.insets;
+        let [w, h] = slice.img.dim();
+        let cols = split_axis(w, [left, right], dst_rect.x(), dst_rect.width());
+        let rows = split_axis(h, [top, bottom], dst_rect.y(), dst_rect.height());
+        for (row_idx, row) in rows.iter().enumerate() {
+            for (col_idx, col) in cols.iter().enumerate() {
+                let slice_mode = match (col_idx, row_idx) {
+                    (1, 1) => slice.center_mode,
+                    (1, _) | (_, 1) => slice.edge_mode,
+                    _ => SliceMode::Stretch,
+                };
+                let tile = slice_mode == SliceMode::Tile;
+                let src = Rect::new([col.0 as i32, row.0 as i32], [col.1, row.1]);
+                let dst = Rect::new([col.2, row.2], [col.3, row.3]);
+                self.draw_slice_using(
+                    mode,
+                    slice,
+                    src,
+                    dst,
+                    [tile && col_idx == 1, tile && row_idx == 1],
+                );
+            }
+        }
+    }
+
+    /// Draws one of the slices, `tile` determines for each axis whether it is repeated instead of
+    /// stretched.
+    fn draw_slice_using<Mode: ImageBlendMode>(
+        &mut self,
+        mode: Mode,
+        slice: &NineSlice,
+        src: Rect,
+        dst: Rect,
+        tile: [bool; 2],
+    ) {
+        if src.is_empty() || dst.is_empty() {
+            return;
+        }
+        match self.map_bounds(dst) {
+            Some(bounds) if bounds.intersect(self.rect()).is_some() => {}
+            _ => return,
+        }
+        let step = [
+            if tile[0] { src.width() } else { dst.width() },
+            if tile[1] { src.height() } else { dst.height() },
+        ];
+        let scale = [
+            step[0] as f32 / src.width() as f32,
+            step[1] as f32 / src.height() as f32,
+        ];
+        let mut y = 0;
+        while y < dst.height() {
+            let mut x = 0;
+            while x < dst.width() {
+                let pos = [dst.x() + (x as i32), dst.y() + (y as i32)];
+                let bounds = Rect::new(
+                    pos,
+                    [step[0].min(dst.width() - x), step[1].min(dst.height() - y)],
+                );
+                // Note: The last tile is cut off, tiles are not scaled along the repeated axes
+                let region = Rect::new(
+                    src.pos,
+                    [
+                        if tile[0] { bounds.width() } else { src.width() },
+                        if tile[1] {
+                            bounds.height()
+                        } else {
+                            src.height()
+                        },
+                    ],
+                );
+                let transform = Affine::translate(-src.x() as f32, -src.y() as f32)
+                    .then_scale(scale[0], scale[1])
+                    .then_translate(pos[0] as f32, pos[1] as f32);
+                self.draw_sampled_using(
+                    mode,
+                    &slice.img,
+                    region,
+                    transform,
+                    bounds,
+                    slice.filter,
+                    SampleEdge::Clamp,
+                );
+                x += step[0];
+            }
+            y += step[1];
+        }
+    }
+}
@@ -0,0 +1,152 @@
+use crate::gradient::Gradient;
+use crate::img::Image;
+use crate::rgba::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Source of the colors used by fills. Implemented by `Rgba` (solid color), `Gradient` (also by reference),
+/// `ImagePattern` and closures of the form `Fn([i32; 2]) -> Rgba`.
+pub trait Paint {
+    /// Color of the pixel at the specified position (using the local coordinates of the canvas,
+    /// see `Canvas::set_transform`).
+    fn color_at(&self, pos: [i32; 2]) -> Rgba;
+
+    /// Color at a point between pixel centers (which are located at `x + 0.5`, `y + 0.5`), used
+    /// when the transformation of the canvas scales or rotates. The default uses the pixel that
+    /// contains the point, gradients are evaluated at the point itself.
+    fn color_at_point(&self, pos: [f32; 2]) -> Rgba {
+        self.color_at([pos[0].floor() as i32, pos[1].floor() as i32])
+    }
+
+    /// Returns the color if every pixel has the same color, which allows faster fills.
+    fn solid_color(&self) -> Option<Rgba> {
+        None
+    }
+
+    /// True if every color is fully opaque, in which case the default fills overwrite the
+    /// existing content instead of blending with it.
+    fn is_opaque(&self) -> bool {
+        false
+    }
+}
+
+impl Paint for Rgba {
+    fn color_at(&self, _pos: [i32; 2]) -> Rgba {
+        *self
+    }
+    fn solid_color(&self) -> Option<Rgba> {
+        Some(*self)
+    }
+    fn is_opaque(&self) -> bool {
+        self.alpha() == 255
+    }
+}
+
+impl Paint for &Gradient {
+    fn color_at(&self, pos: [i32; 2]) -> Rgba {
+        self.pixel_color(pos)
+    }
+    fn color_at_point(&self, pos: [f32; 2]) -> Rgba {
+        Gradient::color_at(self, pos)
+    }
+    fn is_opaque(&self) -> bool {
+        Gradient::is_opaque(self)
+    }
+}
+
+impl Paint for Gradient {
+    fn color_at(&self, pos: [i32; 2]) -> Rgba {
+        self.pixel_color(pos)
+    }
+    fn color_at_point(&self, pos: [f32; 2]) -> Rgba {
+        Gradient::color_at(self, pos)
+    }
+    fn is_opaque(&self) -> bool {
+        Gradient::is_opaque(self)
+    }
+}
+
+impl<F: Fn([i32; 2]) -> Rgba> Paint for F {
+    fn color_at(&self, pos: [i32; 2]) -> Rgba {
+        self(pos)
+    }
+}
+
+/// Determines along which axes an `ImagePattern` is repeated.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum PatternRepeat {
+    Repeat,
+    RepeatX,
+    RepeatY,
+    /// The image is drawn once, everything else is transparent.
+    NoRepeat,
+}
+
+/// Paint that tiles an image. The top-left corner of one of the tiles is located at `offset`.
+#[derive(Clone, Copy)]
+pub struct ImagePattern<'a> {
+    img: &'a Image,
+    offset: [i32; 2],
+    repeat: PatternRepeat,
+}
+
+impl<'a> ImagePattern<'a> {
+    pub fn new(img: &'a Image) -> Self {
+        Self {
+            img,
+            offset: [0, 0],
+            repeat: PatternRepeat::Repeat,
+        }
+    }
+
+    pub fn with_offset(mut self, offset: [i32; 2]) -> Self {
+        self.offset = offset;
+        self
+    }
+
+    pub fn with_repeat(mut self, repeat: PatternRepeat) -> Self {
+        self.repeat = repeat;
+        self
+    }
+
+    pub fn image(&self) -> &'a Image {
+        self.img
+    }
+    pub fn offset(&self) -> [i32; 2] {
+        self.offset
+    }
+    pub fn repeat(&self) -> PatternRepeat {
+        self.repeat
+    }
+}
+
+impl<'a> Paint for ImagePattern<'a> {
+    fn color_at(&self, pos: [i32; 2]) -> Rgba {
+        let [w, h] = self.img.dim();
+        if w == 0 || h == 0 {
+            return Rgba([0, 0, 0, 0]);
+        }
+        let x = (pos[0] as i64) - (self.offset[0] as i64);
+        let y = (pos[1] as i64) - (self.offset[1] as i64);
+        let (repeat_x, repeat_y) = match self.repeat {
+            PatternRepeat::Repeat => (true, true),
+            PatternRepeat::RepeatX => (true, false),
+            PatternRepeat::RepeatY => (false, true),
+            PatternRepeat::NoRepeat => (false, false),
+        };
+        let wrap = |v: i64, len: u32, repeat: bool| {
+            if repeat {
+                Some(v.rem_euclid(len as i64) as u32)
+            } else if v >= 0 && v < (len as i64) {
+                Some(v as u32)
+            } else {
+                None
+            }
+        };
+        match (wrap(x, w, repeat_x), wrap(y, h, repeat_y)) {
+            (Some(x), Some(y)) => self.img.get([x, y]),
+            _ => Rgba([0, 0, 0, 0]),
+        }
+    }
+}
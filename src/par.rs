@@ -0,0 +1,21 @@
+use crate::canvas::Canvas;
+use crate::img::Image;
+use crate::rgba::Rgba;
+use rayon::prelude::*;
+
+impl Image {
+    /// Parallel iterator over the rows of the image.
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [Rgba]> + '_ {
+        let stride = self.stride().max(1);
+        self.buffer_mut().par_chunks_mut(stride)
+    }
+}
+
+impl<'a> Canvas<'a> {
+    /// Parallel iterator over the rows of the canvas. Like `rows_iter_mut`, the rows are not
+    /// clipped. Use `split_rows` to draw with several threads.
+    pub fn par_rows_mut(&mut self) -> impl IndexedParallelIterator<Item = &mut [Rgba]> + '_ {
+        let rows: Vec<&mut [Rgba]> = self.rows_iter_mut().collect();
+        rows.into_par_iter()
+    }
+}
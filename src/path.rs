@@ -0,0 +1,354 @@
+use crate::geom::*;
+use crate::stroke::{circle_segments, FLATTEN_TOLERANCE};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum PathCmd {
+    MoveTo([f32; 2]),
+    LineTo([f32; 2]),
+    QuadTo([f32; 2], [f32; 2]),
+    CubicTo([f32; 2], [f32; 2], [f32; 2]),
+    /// Elliptical arc in center parameterization (angles in radians).
+    Arc {
+        center: [f32; 2],
+        radii: [f32; 2],
+        x_rotation: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+    },
+    Close,
+}
+
+/// Vector path made of one or more sub-paths. Each sub-path starts with `move_to` and may be
+/// closed using `close`. Sub-paths that are not closed are still closed implicitly when filled.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug, Default)]
+pub struct Path {
+    cmds: Vec<PathCmd>,
+    start: [f32; 2],
+    current: [f32; 2],
+}
+
+/// Flattened sub-path, consisting only of line segments.
+pub(crate) struct FlatContour {
+    pub pts: Vec<[f32; 2]>,
+    pub closed: bool,
+}
+
+impl Path {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cmds.is_empty()
+    }
+
+    /// The end point of the last command.
+    pub fn current_pos(&self) -> [f32; 2] {
+        self.current
+    }
+
+    pub fn move_to(&mut self, p: [f32; 2]) -> &mut Self {
+        self.cmds.push(PathCmd::MoveTo(p));
+        self.start = p;
+        self.current = p;
+        self
+    }
+
+    pub fn line_to(&mut self, p: [f32; 2]) -> &mut Self {
+        self.ensure_started();
+        self.cmds.push(PathCmd::LineTo(p));
+        self.current = p;
+        self
+    }
+
+    /// Quadratic Bézier curve from the current position to `p` using the control point `ctrl`.
+    pub fn quad_to(&mut self, ctrl: [f32; 2], p: [f32; 2]) -> &mut Self {
+        self.ensure_started();
+        self.cmds.push(PathCmd::QuadTo(ctrl, p));
+        self.current = p;
+        self
+    }
+
+    /// Cubic Bézier curve from the current position to `p` using the control points `ctrl1` and
+    /// `ctrl2`.
+    pub fn cubic_to(&mut self, ctrl1: [f32; 2], ctrl2: [f32; 2], p: [f32; 2]) -> &mut Self {
+        self.ensure_started();
+        self.cmds.push(PathCmd::CubicTo(ctrl1, ctrl2, p));
+        self.current = p;
+        self
+    }
+
+    /// Elliptical arc from the current position to `p`, using the same parameters as the SVG
+    /// arc command. The rotation of the ellipse's x-axis is in radians. Out-of-range radii are
+    /// scaled up so that the arc can reach the end point. If either radius is zero, then a
+    /// straight line is used.
+    pub fn arc_to(
+        &mut self,
+        radii: [f32; 2],
+        x_rotation: f32,
+        large_arc: bool,
+        sweep: bool,
+        p: [f32; 2],
+    ) -> &mut Self {
+        self.ensure_started();
+        let p0 = self.current;
+        let (rx, ry) = (radii[0].abs(), radii[1].abs());
+        if p0 == p {
+            return self;
+        }
+        if rx == 0.0 || ry == 0.0 {
+            return self.line_to(p);
+        }
+
+        // Conversion from endpoint to center parameterization, see "Elliptical arc
+        // implementation notes" (B.2.4) of the SVG specification
+        let (sin_phi, cos_phi) = x_rotation.sin_cos();
+        let dx = 0.5 * (p0[0] - p[0]);
+        let dy = 0.5 * (p0[1] - p[1]);
+        let x1 = cos_phi * dx + sin_phi * dy;
+        let y1 = -sin_phi * dx + cos_phi * dy;
+
+        let lambda = (x1 * x1) / (rx * rx) + (y1 * y1) / (ry * ry);
+        let (rx, ry) = if lambda > 1.0 {
+            let s = lambda.sqrt();
+            (rx * s, ry * s)
+        } else {
+            (rx, ry)
+        };
+
+        let num = rx * rx * ry * ry - rx * rx * y1 * y1 - ry * ry * x1 * x1;
+        let den = rx * rx * y1 * y1 + ry * ry * x1 * x1;
+        let mut coef = (num / den).max(0.0).sqrt();
+        if large_arc == sweep {
+            coef = -coef;
+        }
+        let cx1 = coef * rx * y1 / ry;
+        let cy1 = -coef * ry * x1 / rx;
+
+        let center = [
+            cos_phi * cx1 - sin_phi * cy1 + 0.5 * (p0[0] + p[0]),
+            sin_phi * cx1 + cos_phi * cy1 + 0.5 * (p0[1] + p[1]),
+        ];
+
+        let angle = |u: [f32; 2], v: [f32; 2]| cross(u, v).atan2(dot(u, v));
+        let u = [(x1 - cx1) / rx, (y1 - cy1) / ry];
+        let v = [(-x1 - cx1) / rx, (-y1 - cy1) / ry];
+        let start_angle = angle([1.0, 0.0], u);
+        let mut sweep_angle = angle(u, v);
+        if !sweep && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        } else if sweep && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        }
+
+        self.cmds.push(PathCmd::Arc {
+            center,
+            radii: [rx, ry],
+            x_rotation,
+            start_angle,
+            sweep_angle,
+        });
+        // Note: Use the exact end point instead of the (slightly imprecise) computed value
+        self.cmds.push(PathCmd::LineTo(p));
+        self.current = p;
+        self
+    }
+
+    /// Elliptical arc around `center`. Angles are in radians, starting at the positive x-axis and
+    /// increasing clockwise (the y-axis points down). If the path has a current sub-path, then
+    /// a line connects it to the start of the arc, otherwise a new sub-path is started.
+    pub fn arc(
+        &mut self,
+        center: [f32; 2],
+        radii: [f32; 2],
+        start_angle: f32,
+        sweep_angle: f32,
+    ) -> &mut Self {
+        let point_at = |a: f32| {
+            [
+                center[0] + radii[0] * a.cos(),
+                center[1] + radii[1] * a.sin(),
+            ]
+        };
+        let start = point_at(start_angle);
+        if self.cmds.is_empty() || self.cmds.last() == Some(&PathCmd::Close) {
+            self.move_to(start);
+        } else if self.current != start {
+            self.line_to(start);
+        }
+        self.cmds.push(PathCmd::Arc {
+            center,
+            radii,
+            x_rotation: 0.0,
+            start_angle,
+            sweep_angle,
+        });
+        self.current = point_at(start_angle + sweep_angle);
+        self
+    }
+
+    /// Adds a closed ellipse as a new sub-path.
+    pub fn ellipse(&mut self, center: [f32; 2], radii: [f32; 2]) -> &mut Self {
+        self.move_to([center[0] + radii[0], center[1]]);
+        self.arc(center, radii, 0.0, 2.0 * PI);
+        self.close()
+    }
+
+    /// Adds a closed rectangle with rounded corners as a new sub-path. The radii are specified
+    /// clockwise, starting at the top left corner. If the radii of two adjacent corners do not fit
+    /// the side between them, then all radii are scaled down proportionally (like CSS does).
+    pub fn rounded_rect(&mut self, pos: [f32; 2], dim: [f32; 2], radii: [f32; 4]) -> &mut Self {
+        let [x, y] = pos;
+        let [w, h] = [dim[0].max(0.0), dim[1].max(0.0)];
+        let mut r = [0.0f32; 4];
+        for (r, v) in r.iter_mut().zip(radii.iter()) {
+            *r = if v.is_finite() { v.max(0.0) } else { 0.0 };
+        }
+        let mut scale = 1.0f32;
+        for &(side, a, b) in [(w, 0, 1), (h, 1, 2), (w, 2, 3), (h, 3, 0)].iter() {
+            let sum = r[a] + r[b];
+            if sum > side {
+                scale = scale.min(side / sum);
+            }
+        }
+        for v in r.iter_mut() {
+            *v *= scale;
+        }
+
+        let corners = [
+            ([x + r[0], y + r[0]], r[0], PI),
+            ([x + w - r[1], y + r[1]], r[1], -0.5 * PI),
+            ([x + w - r[2], y + h - r[2]], r[2], 0.0),
+            ([x + r[3], y + h - r[3]], r[3], 0.5 * PI),
+        ];
+        self.move_to([x + r[0], y]);
+        for &(center, radius, start_angle) in corners[1..].iter().chain(corners[..1].iter()) {
+            if radius > 0.0 {
+                self.arc(center, [radius, radius], start_angle, 0.5 * PI);
+            } else {
+                self.line_to(center);
+            }
+        }
+        self.close()
+    }
+
+    /// Closes the current sub-path by connecting it back to its starting point.
+    pub fn close(&mut self) -> &mut Self {
+        if !self.cmds.is_empty() {
+            self.cmds.push(PathCmd::Close);
+            self.current = self.start;
+        }
+        self
+    }
+
+    fn ensure_started(&mut self) {
+        if self.cmds.is_empty() {
+            self.move_to(self.current);
+        } else if let Some(PathCmd::Close) = self.cmds.last() {
+            // Drawing after a close starts a new sub-path at the same location
+            self.move_to(self.start);
+        }
+    }
+
+    /// Approximates the curves of the path using line segments.
+    pub(crate) fn flatten(&self) -> Vec<FlatContour> {
+        let mut result = Vec::new();
+        let mut cur = FlatContour {
+            pts: Vec::new(),
+            closed: false,
+        };
+        let mut pos = [0.0, 0.0];
+        for cmd in self.cmds.iter().cloned() {
+            match cmd {
+                PathCmd::MoveTo(p) => {
+                    if !cur.pts.is_empty() {
+                        result.push(cur);
+                    }
+                    cur = FlatContour {
+                        pts: vec![p],
+                        closed: false,
+                    };
+                    pos = p;
+                }
+                PathCmd::LineTo(p) => {
+                    cur.pts.push(p);
+                    pos = p;
+                }
+                PathCmd::QuadTo(c, p) => {
+                    let dd = length(add(sub(pos, mul(c, 2.0)), p));
+                    let n = segment_count((dd / (4.0 * FLATTEN_TOLERANCE)).sqrt());
+                    for i in 1..=n {
+                        let t = (i as f32) / (n as f32);
+                        let mt = 1.0 - t;
+                        cur.pts.push(add(
+                            add(mul(pos, mt * mt), mul(c, 2.0 * mt * t)),
+                            mul(p, t * t),
+                        ));
+                    }
+                    pos = p;
+                }
+                PathCmd::CubicTo(c1, c2, p) => {
+                    let dd1 = length(add(sub(pos, mul(c1, 2.0)), c2));
+                    let dd2 = length(add(sub(c1, mul(c2, 2.0)), p));
+                    let dd = dd1.max(dd2);
+                    let n = segment_count((3.0 * dd / (4.0 * FLATTEN_TOLERANCE)).sqrt());
+                    for i in 1..=n {
+                        let t = (i as f32) / (n as f32);
+                        let mt = 1.0 - t;
+                        cur.pts.push(add(
+                            add(mul(pos, mt * mt * mt), mul(c1, 3.0 * mt * mt * t)),
+                            add(mul(c2, 3.0 * mt * t * t), mul(p, t * t * t)),
+                        ));
+                    }
+                    pos = p;
+                }
+                PathCmd::Arc {
+                    center,
+                    radii,
+                    x_rotation,
+                    start_angle,
+                    sweep_angle,
+                } => {
+                    let full = circle_segments(radii[0].max(radii[1])) as f32;
+                    let n = segment_count(full * sweep_angle.abs() / (2.0 * PI));
+                    let (sin_phi, cos_phi) = x_rotation.sin_cos();
+                    for i in 0..=n {
+                        let a = start_angle + sweep_angle * (i as f32) / (n as f32);
+                        let (sin_a, cos_a) = a.sin_cos();
+                        let x = radii[0] * cos_a;
+                        let y = radii[1] * sin_a;
+                        let p = [
+                            center[0] + cos_phi * x - sin_phi * y,
+                            center[1] + sin_phi * x + cos_phi * y,
+                        ];
+                        cur.pts.push(p);
+                        pos = p;
+                    }
+                }
+                PathCmd::Close => {
+                    cur.closed = true;
+                    if let Some(first) = cur.pts.first().cloned() {
+                        pos = first;
+                    }
+                }
+            }
+        }
+        if !cur.pts.is_empty() {
+            result.push(cur);
+        }
+        result
+    }
+}
+
+fn segment_count(n: f32) -> usize {
+    if n.is_finite() {
+        (n.ceil() as usize).clamp(1, 4096)
+    } else {
+        1
+    }
+}
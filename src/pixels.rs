@@ -0,0 +1,70 @@
+use crate::img::Image;
+use crate::rgba::Rgba;
+use std::marker::PhantomData;
+
+/// Mutable access to the pixel buffer of an image. Unlike `&mut [Rgba]`, several views can point
+/// into the same buffer, which allows canvases for disjoint regions of an image (see
+/// `Canvas::split_at_y`). Only the accessed pixels are ever referenced, so views that never
+/// access the same pixels do not alias.
+pub(crate) struct Pixels<'a> {
+    ptr: *mut Rgba,
+    len: usize,
+    marker: PhantomData<&'a mut [Rgba]>,
+}
+
+// Safety: Each view behaves like `&mut [Rgba]` for the pixels that it accesses
+unsafe impl<'a> Send for Pixels<'a> {}
+unsafe impl<'a> Sync for Pixels<'a> {}
+
+impl<'a> Pixels<'a> {
+    pub(crate) fn new(img: &'a mut Image) -> Self {
+        let buf = img.buffer_mut();
+        Pixels {
+            ptr: buf.as_mut_ptr(),
+            len: buf.len(),
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn reborrow(&mut self) -> Pixels<'_> {
+        // Safety: This view is borrowed for the lifetime of the new one
+        unsafe { self.alias() }
+    }
+
+    /// Another view of the same buffer.
+    ///
+    /// Safety: The lifetime must not exceed the lifetime of this view, and views that are used at
+    /// the same time must access disjoint pixels.
+    pub(crate) unsafe fn alias<'b>(&self) -> Pixels<'b> {
+        Pixels {
+            ptr: self.ptr,
+            len: self.len,
+            marker: PhantomData,
+        }
+    }
+
+    pub(crate) fn len(&self) -> usize {
+        self.len
+    }
+    pub(crate) fn as_mut_ptr(&self) -> *mut Rgba {
+        self.ptr
+    }
+
+    pub(crate) fn get(&self, idx: usize) -> Rgba {
+        assert!(idx < self.len, "Index is outside of the buffer");
+        unsafe { *self.ptr.add(idx) }
+    }
+    pub(crate) fn get_mut(&mut self, idx: usize) -> &mut Rgba {
+        assert!(idx < self.len, "Index is outside of the buffer");
+        unsafe { &mut *self.ptr.add(idx) }
+    }
+
+    pub(crate) fn slice(&self, idx: usize, len: usize) -> &[Rgba] {
+        assert!(idx + len <= self.len, "Slice extends beyond the buffer");
+        unsafe { std::slice::from_raw_parts(self.ptr.add(idx), len) }
+    }
+    pub(crate) fn slice_mut(&mut self, idx: usize, len: usize) -> &mut [Rgba] {
+        assert!(idx + len <= self.len, "Slice extends beyond the buffer");
+        unsafe { std::slice::from_raw_parts_mut(self.ptr.add(idx), len) }
+    }
+}
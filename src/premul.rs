@@ -0,0 +1,96 @@
+use crate::rgba::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Color with premultiplied alpha, the color channels are already multiplied by the alpha value
+/// (so they never exceed it). Used to exchange pixels with APIs that expect premultiplied alpha
+/// (like GPU textures), the blend modes work on straight `Rgba` colors.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Copy, Clone, Eq, PartialEq, Hash, Debug, Default)]
+pub struct PremulRgba(pub [u8; 4]);
+
+impl From<Rgba> for PremulRgba {
+    fn from(c: Rgba) -> Self {
+        Self::from_rgba(c)
+    }
+}
+impl From<PremulRgba> for Rgba {
+    fn from(c: PremulRgba) -> Self {
+        c.to_rgba()
+    }
+}
+
+impl PremulRgba {
+    pub fn from_rgba(c: Rgba) -> Self {
+        let [r, g, b, a] = c.rgba();
+        let a32 = a as u32;
+        PremulRgba([
+            mul_255(r as u32, a32) as u8,
+            mul_255(g as u32, a32) as u8,
+            mul_255(b as u32, a32) as u8,
+            a,
+        ])
+    }
+
+    /// Converts back to straight alpha. Note: Colors with a low alpha value lose precision, fully
+    /// transparent colors become `Rgba([0, 0, 0, 0])`.
+    pub fn to_rgba(&self) -> Rgba {
+        let [r, g, b, a] = self.0;
+        match a {
+            0 => Rgba([0, 0, 0, 0]),
+            255 => Rgba(self.0),
+            _ => {
+                let recip = UNPREMULTIPLY[a as usize];
+                Rgba([
+                    unpremultiply(r, recip),
+                    unpremultiply(g, recip),
+                    unpremultiply(b, recip),
+                    a,
+                ])
+            }
+        }
+    }
+
+    pub fn alpha(&self) -> u8 {
+        self.0[3]
+    }
+}
+
+/// Returns `a * b / 255` rounded to the nearest integer, for values up to 255.
+#[inline]
+pub(crate) fn mul_255(a: u32, b: u32) -> u32 {
+    let v = a * b + 128;
+    (v + (v >> 8)) >> 8
+}
+
+/// Interpolates between the colors using premultiplied alpha, `t` ranges from 0 (`a`) to 255
+/// (`b`).
+pub(crate) fn lerp_premul(a: Rgba, b: Rgba, t: u8) -> Rgba {
+    let wa = (a.alpha() as u64) * (255 - t as u64);
+    let wb = (b.alpha() as u64) * (t as u64);
+    let w = wa + wb;
+    if w == 0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    let (a, b) = (a.rgba(), b.rgba());
+    let mix = |i: usize| (((a[i] as u64) * wa + (b[i] as u64) * wb + w / 2) / w) as u8;
+    Rgba([mix(0), mix(1), mix(2), ((w + 127) / 255) as u8])
+}
+
+#[inline]
+fn unpremultiply(v: u8, recip: u32) -> u8 {
+    (((v as u32) * recip + (1 << 15)) >> 16).min(255) as u8
+}
+
+/// Fixed-point reciprocals `255 / alpha` (16 fractional bits), which avoid a divide per channel.
+const UNPREMULTIPLY: [u32; 256] = unpremultiply_table();
+
+const fn unpremultiply_table() -> [u32; 256] {
+    let mut table = [0; 256];
+    let mut a = 1;
+    while a < 256 {
+        table[a] = ((255 << 16) + (a as u32) / 2) / (a as u32);
+        a += 1;
+    }
+    table
+}
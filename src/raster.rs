@@ -0,0 +1,239 @@
+use crate::rect::Rect;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Determines which areas are considered inside of a shape made of multiple (possibly
+/// overlapping) contours.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum FillRule {
+    /// Inside when the contours wind around the point a non-zero number of times. Holes must be
+    /// defined using the opposite orientation of the outer contour.
+    NonZero,
+    /// Inside when a ray from the point crosses an odd number of contour edges. Holes can use any
+    /// orientation.
+    EvenOdd,
+}
+
+/// Coverage rasterizer for polygons. Every edge adds its signed area to an accumulation buffer,
+/// the running sum along a row is then the (winding weighted) coverage of each pixel. This is
+/// the same approach used by font-rs and FreeType's gray rasterizer.
+pub(crate) struct Rasterizer {
+    rect: Rect,
+    stride: usize,
+    acc: Vec<f32>,
+}
+impl Rasterizer {
+    /// Creates a rasterizer for the specified pixel region. Edges outside of the region still
+    /// contribute to the winding of the pixels within the region.
+    pub fn new(rect: Rect) -> Self {
+        // Note: Two extra entries per row, edges on the right border write one past the width
+        let stride = (rect.width() as usize) + 2;
+        let len = stride * (rect.height() as usize);
+        Self {
+            rect,
+            stride,
+            acc: vec![0.0; len],
+        }
+    }
+
+    /// Creates a rasterizer that covers the overlap of the contours' bounding box and the clip
+    /// rectangle. If there is no overlap, then None is returned.
+    pub fn for_contours<P: AsRef<[[f32; 2]]>>(clip: Rect, contours: &[P]) -> Option<Self> {
+        let rect = bounding_rect(contours.iter().map(|c| c.as_ref()))?;
+        let rect = rect.intersect(clip)?;
+        let mut r = Self::new(rect);
+        for c in contours.iter() {
+            r.add_contour(c.as_ref());
+        }
+        Some(r)
+    }
+
+    /// Pixel region of the rasterizer.
+    pub fn rect(&self) -> Rect {
+        self.rect
+    }
+
+    /// Adds a closed contour (the last point connects back to the first point).
+    pub fn add_contour(&mut self, pts: &[[f32; 2]]) {
+        if pts.len() < 2 {
+            return;
+        }
+        let mut prev = pts[pts.len() - 1];
+        for p in pts.iter().cloned() {
+            self.add_line(prev, p);
+            prev = p;
+        }
+    }
+
+    pub fn add_line(&mut self, p0: [f32; 2], p1: [f32; 2]) {
+        let ox = self.rect.x() as f32;
+        let oy = self.rect.y() as f32;
+        let w = self.rect.width() as f32;
+        let h = self.rect.height() as f32;
+        let a = [p0[0] - ox, p0[1] - oy];
+        let b = [p1[0] - ox, p1[1] - oy];
+        if !(a[0].is_finite() && a[1].is_finite() && b[0].is_finite() && b[1].is_finite()) {
+            return;
+        }
+        if a[1] == b[1] {
+            // Horizontal edges do not contribute
+            return;
+        }
+
+        // Only the part within the rows is relevant
+        let t_top = -a[1] / (b[1] - a[1]);
+        let t_bottom = (h - a[1]) / (b[1] - a[1]);
+        let t0 = t_top.min(t_bottom).max(0.0);
+        let t1 = t_top.max(t_bottom).min(1.0);
+        if t0 >= t1 {
+            return;
+        }
+        let lerp = |t: f32| [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t];
+        let (a, b) = (lerp(t0), lerp(t1));
+
+        // Split at the left and right borders. Parts outside of the region are moved onto the
+        // border, which keeps their contribution to the winding of the row.
+        let mut pts = [a; 4];
+        let mut count = 1;
+        let mut splits = [(0.0f32, [0.0f32; 2]); 2];
+        let mut split_count = 0;
+        for &x in [0.0, w].iter() {
+            if (a[0] - x) * (b[0] - x) < 0.0 {
+                let t = (x - a[0]) / (b[0] - a[0]);
+                splits[split_count] = (t, [x, a[1] + (b[1] - a[1]) * t]);
+                split_count += 1;
+            }
+        }
+        if split_count == 2 && splits[0].0 > splits[1].0 {
+            splits.swap(0, 1);
+        }
+        for (_, p) in splits[..split_count].iter() {
+            pts[count] = *p;
+            count += 1;
+        }
+        pts[count] = b;
+        count += 1;
+
+        for i in 1..count {
+            let mut s0 = pts[i - 1];
+            let mut s1 = pts[i];
+            s0[0] = s0[0].clamp(0.0, w);
+            s1[0] = s1[0].clamp(0.0, w);
+            self.accumulate_line(s0, s1);
+        }
+    }
+
+    fn accumulate_line(&mut self, p0: [f32; 2], p1: [f32; 2]) {
+        if (p0[1] - p1[1]).abs() <= f32::EPSILON {
+            return;
+        }
+        let (dir, p0, p1) = if p0[1] < p1[1] {
+            (1.0, p0, p1)
+        } else {
+            (-1.0, p1, p0)
+        };
+        let w = self.rect.width() as f32;
+        let dxdy = (p1[0] - p0[0]) / (p1[1] - p0[1]);
+        let mut x = p0[0];
+        let y0 = p0[1] as usize;
+        let y1 = (self.rect.height() as usize).min(p1[1].ceil() as usize);
+        for y in y0..y1 {
+            let row = y * self.stride;
+            let dy = ((y + 1) as f32).min(p1[1]) - (y as f32).max(p0[1]);
+            let x_next = (x + dxdy * dy).clamp(0.0, w);
+            let d = dy * dir;
+            let (x0, x1) = if x < x_next { (x, x_next) } else { (x_next, x) };
+            let x0_floor = x0.floor();
+            let x0i = x0_floor as usize;
+            let x1_ceil = x1.ceil();
+            let x1i = x1_ceil as usize;
+            let acc = &mut self.acc[row..(row + self.stride)];
+            if x1i <= x0i + 1 {
+                // The edge stays within a single pixel column
+                let xmf = 0.5 * (x + x_next) - x0_floor;
+                acc[x0i] += d - d * xmf;
+                acc[x0i + 1] += d * xmf;
+            } else {
+                let s = 1.0 / (x1 - x0);
+                let x0f = x0 - x0_floor;
+                let a0 = 0.5 * s * (1.0 - x0f) * (1.0 - x0f);
+                let x1f = x1 - x1_ceil + 1.0;
+                let am = 0.5 * s * x1f * x1f;
+                acc[x0i] += d * a0;
+                if x1i == x0i + 2 {
+                    acc[x0i + 1] += d * (1.0 - a0 - am);
+                } else {
+                    let a1 = s * (1.5 - x0f);
+                    acc[x0i + 1] += d * (a1 - a0);
+                    for v in acc[(x0i + 2)..(x1i - 1)].iter_mut() {
+                        *v += d * s;
+                    }
+                    let a2 = a1 + ((x1i - x0i - 3) as f32) * s;
+                    acc[x1i - 1] += d * (1.0 - a2 - am);
+                }
+                acc[x1i] += d * am;
+            }
+            x = x_next;
+        }
+    }
+
+    /// Calls `f` for every pixel with a non-zero coverage.
+    pub fn for_each_coverage(&self, rule: FillRule, mut f: impl FnMut([i32; 2], u8)) {
+        let width = self.rect.width() as usize;
+        let [x0, y0] = self.rect.pos;
+        for (y, row) in self.acc.chunks(self.stride).enumerate() {
+            let mut sum = 0.0;
+            for (x, v) in row[..width].iter().enumerate() {
+                sum += *v;
+                let alpha = coverage_to_alpha(rule, sum);
+                if alpha > 0 {
+                    f([x0 + (x as i32), y0 + (y as i32)], alpha);
+                }
+            }
+        }
+    }
+}
+
+fn coverage_to_alpha(rule: FillRule, winding: f32) -> u8 {
+    let coverage = match rule {
+        FillRule::NonZero => winding.abs(),
+        FillRule::EvenOdd => {
+            // Every full turn toggles between inside and outside
+            let v = winding.abs() % 2.0;
+            if v > 1.0 {
+                2.0 - v
+            } else {
+                v
+            }
+        }
+    };
+    if coverage >= 1.0 {
+        255
+    } else {
+        (coverage * 255.0 + 0.5) as u8
+    }
+}
+
+/// Smallest pixel rectangle that contains all of the points.
+pub(crate) fn bounding_rect<'a>(contours: impl Iterator<Item = &'a [[f32; 2]]>) -> Option<Rect> {
+    let mut min = [f32::MAX, f32::MAX];
+    let mut max = [f32::MIN, f32::MIN];
+    for p in contours.flat_map(|c| c.iter()) {
+        if !(p[0].is_finite() && p[1].is_finite()) {
+            continue;
+        }
+        min = [min[0].min(p[0]), min[1].min(p[1])];
+        max = [max[0].max(p[0]), max[1].max(p[1])];
+    }
+    if min[0] > max[0] {
+        return None;
+    }
+    // Note: Limit the range so that the conversion to i32 can not overflow
+    const LIMIT: f32 = (1 << 29) as f32;
+    let x0 = min[0].clamp(-LIMIT, LIMIT).floor() as i32;
+    let y0 = min[1].clamp(-LIMIT, LIMIT).floor() as i32;
+    let x1 = max[0].clamp(-LIMIT, LIMIT).ceil() as i32;
+    let y1 = max[1].clamp(-LIMIT, LIMIT).ceil() as i32;
+    Some(Rect::new([x0, y0], [(x1 - x0) as u32, (y1 - y0) as u32]))
+}
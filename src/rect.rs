@@ -1,4 +1,4 @@
-use std::cmp::max;
+use std::cmp::{max, min};
 use std::fmt;
 #[cfg(feature = "serde")]
 use serde::{Deserialize, Serialize};
@@ -73,6 +73,33 @@ impl Rect {
         }
     }
 
+    /// Returns the overlapping area of the two rectangles, or None if they do not overlap.
+    pub fn intersect(&self, other: Rect) -> Option<Rect> {
+        let x = max(self.x(), other.x());
+        let y = max(self.y(), other.y());
+        let end_x = min(self.end_x(), other.end_x());
+        let end_y = min(self.end_y(), other.end_y());
+        if x >= end_x || y >= end_y {
+            None
+        } else {
+            Some(Rect::new([x, y], [(end_x - x) as u32, (end_y - y) as u32]))
+        }
+    }
+
+    /// Returns the smallest rectangle that contains both rectangles. Empty rectangles are ignored.
+    pub fn union(&self, other: Rect) -> Rect {
+        if other.is_empty() {
+            return *self;
+        } else if self.is_empty() {
+            return other;
+        }
+        let x = min(self.x(), other.x());
+        let y = min(self.y(), other.y());
+        let end_x = max(self.end_x(), other.end_x());
+        let end_y = max(self.end_y(), other.end_y());
+        Rect::new([x, y], [(end_x - x) as u32, (end_y - y) as u32])
+    }
+
     #[must_use]
     pub fn with_pos(&self, pos: [i32; 2]) -> Self {
         Self { pos, dim: self.dim }
@@ -34,6 +34,15 @@ fn srgb_to_linear(v: f32) -> f32 {
     }
 }
 
+fn linear_to_srgb(v: f32) -> f32 {
+    // Inverse of srgb_to_linear
+    if v <= 0.0031308 {
+        v * 12.92
+    } else {
+        1.055 * v.powf(1.0 / 2.4) - 0.055
+    }
+}
+
 impl std::str::FromStr for Rgba {
     type Err = RgbaParseError;
 
@@ -111,6 +120,18 @@ impl Rgba {
         ]
     }
 
+    /// Converts linear color components (0.0 to 1.0) to sRGB, the alpha value is not changed.
+    /// This is the inverse of `srgb_to_linear_f32`.
+    pub fn from_linear_f32(rgba: [f32; 4]) -> Rgba {
+        let [r, g, b, a] = rgba;
+        Rgba([
+            f32_to_u8(linear_to_srgb(r)),
+            f32_to_u8(linear_to_srgb(g)),
+            f32_to_u8(linear_to_srgb(b)),
+            f32_to_u8(a),
+        ])
+    }
+
     pub fn with_alpha(&self, alpha: u8) -> Rgba {
         let c = self.0;
         Rgba([c[0], c[1], c[2], alpha])
@@ -1,8 +1,14 @@
 use crate::img::Image;
+use crate::pixels::Pixels;
 use crate::rgba::Rgba;
+use std::marker::PhantomData;
 
+// Note: The iterators keep a raw pointer instead of a slice, so that they only ever reference
+// the rows of the region (other parts of the buffer may belong to a different canvas)
 pub struct RowsIter<'a> {
-    buf: &'a [Rgba],
+    ptr: *const Rgba,
+    len: usize,
+    marker: PhantomData<&'a [Rgba]>,
     cur_idx: usize,
     cur_pos: [i32; 2],
     width: usize,
@@ -32,8 +38,11 @@ impl<'a> RowsIter<'a> {
         let idx0 = img.index_at([x, y]);
         let stride = img.stride();
         let width = width as usize;
+        let buf = img.buffer();
         RowsIter {
-            buf: img.buffer(),
+            ptr: buf.as_ptr(),
+            len: buf.len(),
+            marker: PhantomData,
             cur_idx: idx0,
             cur_pos: [pos[0] as i32, pos[1] as i32],
             width: width,
@@ -50,7 +59,9 @@ impl<'a> RowsIter<'a> {
         max_idx: usize,
     ) -> RowsIter<'a> {
         RowsIter {
-            buf: buf,
+            ptr: buf.as_ptr(),
+            len: buf.len(),
+            marker: PhantomData,
             cur_idx: idx0,
             cur_pos: pos0,
             width: width,
@@ -58,6 +69,26 @@ impl<'a> RowsIter<'a> {
             max_idx: max_idx,
         }
     }
+    /// Rows of the pixels, see `unchecked_from_index`.
+    pub(crate) unsafe fn from_pixels(
+        pixels: &'a Pixels,
+        idx0: usize,
+        pos0: [i32; 2],
+        width: usize,
+        stride: usize,
+        max_idx: usize,
+    ) -> RowsIter<'a> {
+        RowsIter {
+            ptr: pixels.as_mut_ptr(),
+            len: pixels.len(),
+            marker: PhantomData,
+            cur_idx: idx0,
+            cur_pos: pos0,
+            width,
+            stride,
+            max_idx,
+        }
+    }
     pub fn with_pos(self) -> RowsPosIter<'a> {
         RowsPosIter(self)
     }
@@ -66,17 +97,25 @@ impl<'a> Iterator for RowsIter<'a> {
     type Item = &'a [Rgba];
 
     fn next(&mut self) -> Option<&'a [Rgba]> {
-        if self.cur_idx <= self.max_idx {
-            let result = &self.buf[self.cur_idx..(self.cur_idx + self.width)];
+        if self.cur_idx < self.max_idx {
+            let (from_idx, to_idx) = (self.cur_idx, self.cur_idx + self.width);
+            assert!(to_idx <= self.len, "Row extends beyond the buffer");
             self.cur_idx += self.stride;
             self.cur_pos[1] += 1;
-            Some(result)
+            unsafe {
+                let row = std::slice::from_raw_parts(self.ptr.add(from_idx), to_idx - from_idx);
+                Some(row)
+            }
         } else {
             None
         }
     }
 }
 
+// Safety: The iterators behave like the slices they were created from
+unsafe impl<'a> Send for RowsIter<'a> {}
+unsafe impl<'a> Sync for RowsIter<'a> {}
+
 pub struct RowsPosIter<'a>(RowsIter<'a>);
 impl<'a> Iterator for RowsPosIter<'a> {
     type Item = ([i32; 2], &'a [Rgba]);
@@ -92,7 +131,9 @@ impl<'a> Iterator for RowsPosIter<'a> {
 }
 
 pub struct RowsMutIter<'a> {
-    buf: &'a mut [Rgba],
+    ptr: *mut Rgba,
+    len: usize,
+    marker: PhantomData<&'a mut [Rgba]>,
     cur_idx: usize,
     cur_pos: [i32; 2],
     width: usize,
@@ -122,8 +163,11 @@ impl<'a> RowsMutIter<'a> {
         let idx0 = img.index_at([x, y]);
         let stride = img.stride();
         let width = width as usize;
+        let buf = img.buffer_mut();
         RowsMutIter {
-            buf: img.buffer_mut(),
+            ptr: buf.as_mut_ptr(),
+            len: buf.len(),
+            marker: PhantomData,
             cur_idx: idx0,
             cur_pos: [pos[0] as i32, pos[1] as i32],
             width: width,
@@ -140,7 +184,9 @@ impl<'a> RowsMutIter<'a> {
         max_idx: usize,
     ) -> RowsMutIter<'a> {
         RowsMutIter {
-            buf: buf,
+            ptr: buf.as_mut_ptr(),
+            len: buf.len(),
+            marker: PhantomData,
             cur_idx: idx0,
             cur_pos: pos0,
             width: width,
@@ -148,6 +194,26 @@ impl<'a> RowsMutIter<'a> {
             max_idx: max_idx,
         }
     }
+    /// Rows of the pixels, see `unchecked_from_index`.
+    pub(crate) unsafe fn from_pixels(
+        pixels: &'a mut Pixels,
+        idx0: usize,
+        pos0: [i32; 2],
+        width: usize,
+        stride: usize,
+        max_idx: usize,
+    ) -> RowsMutIter<'a> {
+        RowsMutIter {
+            ptr: pixels.as_mut_ptr(),
+            len: pixels.len(),
+            marker: PhantomData,
+            cur_idx: idx0,
+            cur_pos: pos0,
+            width,
+            stride,
+            max_idx,
+        }
+    }
     pub fn with_pos(self) -> RowsMutPosIter<'a> {
         RowsMutPosIter(self)
     }
@@ -158,13 +224,13 @@ impl<'a> Iterator for RowsMutIter<'a> {
     fn next(&mut self) -> Option<&'a mut [Rgba]> {
         if self.cur_idx < self.max_idx {
             let (from_idx, to_idx) = (self.cur_idx, self.cur_idx + self.width);
+            assert!(to_idx <= self.len, "Row extends beyond the buffer");
             self.cur_idx += self.stride;
             self.cur_pos[1] += 1;
             unsafe {
                 // Note: This is safe assuming width is less/equal to stride (verified in new method)
-                let slice = &mut self.buf[from_idx..to_idx];
-                let raw = slice as *mut [Rgba];
-                Some(&mut *raw)
+                let row = std::slice::from_raw_parts_mut(self.ptr.add(from_idx), to_idx - from_idx);
+                Some(row)
             }
         } else {
             None
@@ -172,6 +238,9 @@ impl<'a> Iterator for RowsMutIter<'a> {
     }
 }
 
+unsafe impl<'a> Send for RowsMutIter<'a> {}
+unsafe impl<'a> Sync for RowsMutIter<'a> {}
+
 pub struct RowsMutPosIter<'a>(RowsMutIter<'a>);
 impl<'a> Iterator for RowsMutPosIter<'a> {
     type Item = ([i32; 2], &'a mut [Rgba]);
@@ -0,0 +1,131 @@
+use crate::img::Image;
+use crate::rect::Rect;
+use crate::rgba::Rgba;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// Interpolation used when an image is sampled at positions between pixel centers.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum SampleFilter {
+    /// Uses the pixel that contains the position.
+    Nearest,
+    /// Linear interpolation between the four closest pixels.
+    Bilinear,
+    /// Catmull-Rom interpolation between the sixteen closest pixels.
+    Bicubic,
+}
+
+/// Determines how the interpolating filters treat pixels beyond the border of the sampled region.
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub(crate) enum SampleEdge {
+    /// Pixels beyond the border are transparent, which results in smooth edges.
+    Transparent,
+    /// Pixels beyond the border repeat the closest pixel of the region, which keeps the edges
+    /// opaque where adjacent regions are drawn next to each other.
+    Clamp,
+}
+
+/// Samples the region of the image at the specified position (pixel centers are located at
+/// `x + 0.5`, `y + 0.5`). The region must lie within the image. Returns None if the position is
+/// outside of the region.
+pub(crate) fn sample(
+    img: &Image,
+    region: Rect,
+    pos: [f32; 2],
+    filter: SampleFilter,
+    edge: SampleEdge,
+) -> Option<Rgba> {
+    let min = [region.x() as f32, region.y() as f32];
+    let max = [region.end_x() as f32, region.end_y() as f32];
+    if !(pos[0] >= min[0] && pos[1] >= min[1] && pos[0] < max[0] && pos[1] < max[1]) {
+        return None;
+    }
+    match filter {
+        SampleFilter::Nearest => {
+            let x = (pos[0] as i32).min(region.end_x() - 1);
+            let y = (pos[1] as i32).min(region.end_y() - 1);
+            Some(img.get([x, y]))
+        }
+        SampleFilter::Bilinear => {
+            let (x0, fx) = split(pos[0] - 0.5);
+            let (y0, fy) = split(pos[1] - 0.5);
+            let wx = [1.0 - fx, fx];
+            let wy = [1.0 - fy, fy];
+            Some(weighted_sum(img, region, edge, [x0, y0], &wx, &wy))
+        }
+        SampleFilter::Bicubic => {
+            let (x0, fx) = split(pos[0] - 0.5);
+            let (y0, fy) = split(pos[1] - 0.5);
+            Some(weighted_sum(
+                img,
+                region,
+                edge,
+                [x0 - 1, y0 - 1],
+                &catmull_rom(fx),
+                &catmull_rom(fy),
+            ))
+        }
+    }
+}
+
+fn split(v: f32) -> (i32, f32) {
+    let f = v.floor();
+    (f as i32, v - f)
+}
+
+fn catmull_rom(t: f32) -> [f32; 4] {
+    let t2 = t * t;
+    let t3 = t2 * t;
+    [
+        0.5 * (-t + 2.0 * t2 - t3),
+        0.5 * (2.0 - 5.0 * t2 + 3.0 * t3),
+        0.5 * (t + 4.0 * t2 - 3.0 * t3),
+        0.5 * (t3 - t2),
+    ]
+}
+
+/// Sums the premultiplied colors of the pixels starting at `pos0` using the separable weights.
+fn weighted_sum(
+    img: &Image,
+    region: Rect,
+    edge: SampleEdge,
+    pos0: [i32; 2],
+    wx: &[f32],
+    wy: &[f32],
+) -> Rgba {
+    let mut sum = [0.0f32; 4];
+    for (dy, wy) in wy.iter().enumerate() {
+        for (dx, wx) in wx.iter().enumerate() {
+            let weight = wx * wy;
+            if weight == 0.0 {
+                continue;
+            }
+            let mut pos = [pos0[0] + (dx as i32), pos0[1] + (dy as i32)];
+            if edge == SampleEdge::Clamp {
+                pos[0] = pos[0].clamp(region.x(), region.end_x() - 1);
+                pos[1] = pos[1].clamp(region.y(), region.end_y() - 1);
+            }
+            if region.contains(pos) {
+                let [r, g, b, a] = img.get(pos).rgba_f32();
+                let wa = weight * a;
+                sum[0] += r * wa;
+                sum[1] += g * wa;
+                sum[2] += b * wa;
+                sum[3] += wa;
+            }
+        }
+    }
+    let alpha = sum[3].min(1.0);
+    if alpha <= 0.0 {
+        return Rgba([0, 0, 0, 0]);
+    }
+    // Note: The bicubic weights can overshoot, keep the colors within the valid range
+    let inv = 1.0 / sum[3];
+    Rgba::from_f32([
+        (sum[0] * inv).clamp(0.0, 1.0),
+        (sum[1] * inv).clamp(0.0, 1.0),
+        (sum[2] * inv).clamp(0.0, 1.0),
+        alpha,
+    ])
+}
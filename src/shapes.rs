@@ -0,0 +1,358 @@
+use crate::blend::{ColorAlphaBlendMode, ColorAlphaBlendTransparent};
+use crate::canvas::Canvas;
+use crate::paint::Paint;
+use crate::path::Path;
+use crate::raster::FillRule;
+use crate::rect::Rect;
+use crate::stroke::{LineCap, StrokeStyle};
+
+// Note: Angles are in radians, starting at the positive x-axis and increasing clockwise (the
+// y-axis points down). Outlines are centered on the edge of the shape, half of the width is
+// drawn inside the shape and the other half outside.
+
+impl<'a> Canvas<'a> {
+    pub fn fill_circle<P: Paint>(&mut self, center: [f32; 2], radius: f32, paint: P) {
+        self.fill_circle_using(ColorAlphaBlendTransparent, center, radius, paint);
+    }
+
+    pub fn fill_circle_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radius: f32,
+        paint: P,
+    ) {
+        self.fill_ellipse_using(mode, center, [radius, radius], paint);
+    }
+
+    pub fn draw_circle<P: Paint>(&mut self, center: [f32; 2], radius: f32, width: f32, paint: P) {
+        self.draw_circle_using(ColorAlphaBlendTransparent, center, radius, width, paint);
+    }
+
+    pub fn draw_circle_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radius: f32,
+        width: f32,
+        paint: P,
+    ) {
+        self.draw_ellipse_using(mode, center, [radius, radius], width, paint);
+    }
+
+    pub fn fill_ellipse<P: Paint>(&mut self, center: [f32; 2], radii: [f32; 2], paint: P) {
+        self.fill_ellipse_using(ColorAlphaBlendTransparent, center, radii, paint);
+    }
+
+    pub fn fill_ellipse_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radii: [f32; 2],
+        paint: P,
+    ) {
+        let mut p = Path::new();
+        p.ellipse(center, radii);
+        self.fill_path_using(mode, &p, FillRule::NonZero, paint);
+    }
+
+    pub fn draw_ellipse<P: Paint>(
+        &mut self,
+        center: [f32; 2],
+        radii: [f32; 2],
+        width: f32,
+        paint: P,
+    ) {
+        self.draw_ellipse_using(ColorAlphaBlendTransparent, center, radii, width, paint);
+    }
+
+    pub fn draw_ellipse_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radii: [f32; 2],
+        width: f32,
+        paint: P,
+    ) {
+        let mut p = Path::new();
+        p.ellipse(center, radii);
+        self.stroke_path_using(mode, &p, &StrokeStyle::new(width), paint);
+    }
+
+    /// Draws the outline of a circular arc (without the lines to the center).
+    pub fn draw_arc<P: Paint>(
+        &mut self,
+        center: [f32; 2],
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        width: f32,
+        paint: P,
+    ) {
+        self.draw_arc_using(
+            ColorAlphaBlendTransparent,
+            center,
+            radius,
+            start_angle,
+            sweep_angle,
+            width,
+            paint,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_arc_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        width: f32,
+        paint: P,
+    ) {
+        let mut p = Path::new();
+        p.arc(center, [radius, radius], start_angle, sweep_angle);
+        let style = StrokeStyle::new(width).with_cap(LineCap::Butt);
+        self.stroke_path_using(mode, &p, &style, paint);
+    }
+
+    /// Fills a pie slice, the area between the arc and the center.
+    pub fn fill_pie<P: Paint>(
+        &mut self,
+        center: [f32; 2],
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        paint: P,
+    ) {
+        self.fill_pie_using(
+            ColorAlphaBlendTransparent,
+            center,
+            radius,
+            start_angle,
+            sweep_angle,
+            paint,
+        );
+    }
+
+    pub fn fill_pie_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        paint: P,
+    ) {
+        let p = pie_path(center, radius, start_angle, sweep_angle);
+        self.fill_path_using(mode, &p, FillRule::NonZero, paint);
+    }
+
+    pub fn draw_pie<P: Paint>(
+        &mut self,
+        center: [f32; 2],
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        width: f32,
+        paint: P,
+    ) {
+        self.draw_pie_using(
+            ColorAlphaBlendTransparent,
+            center,
+            radius,
+            start_angle,
+            sweep_angle,
+            width,
+            paint,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_pie_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        width: f32,
+        paint: P,
+    ) {
+        let p = pie_path(center, radius, start_angle, sweep_angle);
+        self.stroke_path_using(mode, &p, &StrokeStyle::new(width), paint);
+    }
+
+    /// Fills a segment of a ring (the area between two arcs with the same center).
+    pub fn fill_donut_segment<P: Paint>(
+        &mut self,
+        center: [f32; 2],
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        paint: P,
+    ) {
+        self.fill_donut_segment_using(
+            ColorAlphaBlendTransparent,
+            center,
+            inner_radius,
+            outer_radius,
+            start_angle,
+            sweep_angle,
+            paint,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn fill_donut_segment_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        paint: P,
+    ) {
+        let p = donut_path(center, inner_radius, outer_radius, start_angle, sweep_angle);
+        self.fill_path_using(mode, &p, FillRule::NonZero, paint);
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_donut_segment<P: Paint>(
+        &mut self,
+        center: [f32; 2],
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        width: f32,
+        paint: P,
+    ) {
+        self.draw_donut_segment_using(
+            ColorAlphaBlendTransparent,
+            center,
+            inner_radius,
+            outer_radius,
+            start_angle,
+            sweep_angle,
+            width,
+            paint,
+        );
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn draw_donut_segment_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        center: [f32; 2],
+        inner_radius: f32,
+        outer_radius: f32,
+        start_angle: f32,
+        sweep_angle: f32,
+        width: f32,
+        paint: P,
+    ) {
+        let p = donut_path(center, inner_radius, outer_radius, start_angle, sweep_angle);
+        self.stroke_path_using(mode, &p, &StrokeStyle::new(width), paint);
+    }
+
+    /// Fills a rectangle with rounded corners. The radii are specified clockwise, starting at the
+    /// top left corner.
+    pub fn fill_rounded_rect<P: Paint>(&mut self, rect: Rect, radii: [f32; 4], paint: P) {
+        self.fill_rounded_rect_using(ColorAlphaBlendTransparent, rect, radii, paint);
+    }
+
+    pub fn fill_rounded_rect_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        rect: Rect,
+        radii: [f32; 4],
+        paint: P,
+    ) {
+        let mut p = Path::new();
+        p.rounded_rect(
+            [rect.x() as f32, rect.y() as f32],
+            [rect.width() as f32, rect.height() as f32],
+            radii,
+        );
+        self.fill_path_using(mode, &p, FillRule::NonZero, paint);
+    }
+
+    /// Draws the outline of a rectangle with rounded corners. Like `draw_rect`, the outline stays
+    /// within the rectangle, the radii describe the outer edge.
+    pub fn draw_rounded_rect<P: Paint>(
+        &mut self,
+        rect: Rect,
+        radii: [f32; 4],
+        width: f32,
+        paint: P,
+    ) {
+        self.draw_rounded_rect_using(ColorAlphaBlendTransparent, rect, radii, width, paint);
+    }
+
+    pub fn draw_rounded_rect_using<Mode: ColorAlphaBlendMode, P: Paint>(
+        &mut self,
+        mode: Mode,
+        rect: Rect,
+        radii: [f32; 4],
+        width: f32,
+        paint: P,
+    ) {
+        if !(width > 0.0 && width.is_finite()) {
+            return;
+        }
+        // Note: The outline is the area between the outer edge and the edge inset by the width.
+        // Stroking the center line would lose the rounding of radii smaller than half the width.
+        let [x, y] = [rect.x() as f32, rect.y() as f32];
+        let [w, h] = [rect.width() as f32, rect.height() as f32];
+        let mut p = Path::new();
+        p.rounded_rect([x, y], [w, h], radii);
+        p.rounded_rect(
+            [x + width, y + width],
+            [w - 2.0 * width, h - 2.0 * width],
+            [
+                radii[0] - width,
+                radii[1] - width,
+                radii[2] - width,
+                radii[3] - width,
+            ],
+        );
+        self.fill_path_using(mode, &p, FillRule::EvenOdd, paint);
+    }
+}
+
+fn pie_path(center: [f32; 2], radius: f32, start_angle: f32, sweep_angle: f32) -> Path {
+    let mut p = Path::new();
+    p.move_to(center)
+        .arc(center, [radius, radius], start_angle, sweep_angle)
+        .close();
+    p
+}
+
+fn donut_path(
+    center: [f32; 2],
+    inner_radius: f32,
+    outer_radius: f32,
+    start_angle: f32,
+    sweep_angle: f32,
+) -> Path {
+    // Note: The inner arc runs in the opposite direction, which results in a hole for a full ring
+    let mut p = Path::new();
+    p.arc(
+        center,
+        [outer_radius, outer_radius],
+        start_angle,
+        sweep_angle,
+    )
+    .arc(
+        center,
+        [inner_radius, inner_radius],
+        start_angle + sweep_angle,
+        -sweep_angle,
+    )
+    .close();
+    p
+}
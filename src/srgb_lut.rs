@@ -0,0 +1,51 @@
+use std::sync::OnceLock;
+
+/// Number of entries of the table that converts linear values back to sRGB.
+const LINEAR_STEPS: usize = 4096;
+
+/// Lookup tables for converting between sRGB and linear light, linear values range from 0 to
+/// 65535.
+struct Tables {
+    to_linear: [u16; 256],
+    to_srgb: [u8; LINEAR_STEPS],
+}
+
+fn tables() -> &'static Tables {
+    static TABLES: OnceLock<Tables> = OnceLock::new();
+    TABLES.get_or_init(|| {
+        let mut to_linear = [0; 256];
+        for (i, v) in to_linear.iter_mut().enumerate() {
+            let srgb = (i as f32) / 255.0;
+            let linear = if srgb <= 0.04045 {
+                srgb / 12.92
+            } else {
+                ((srgb + 0.055) / 1.055).powf(2.4)
+            };
+            *v = (linear * 65535.0 + 0.5) as u16;
+        }
+        let mut to_srgb = [0; LINEAR_STEPS];
+        for (i, v) in to_srgb.iter_mut().enumerate() {
+            let linear = (i as f32) / ((LINEAR_STEPS - 1) as f32);
+            let srgb = if linear <= 0.0031308 {
+                linear * 12.92
+            } else {
+                1.055 * linear.powf(1.0 / 2.4) - 0.055
+            };
+            *v = (srgb * 255.0 + 0.5).clamp(0.0, 255.0) as u8;
+        }
+        Tables { to_linear, to_srgb }
+    })
+}
+
+/// Converts the sRGB color components to linear light (0 to 65535).
+pub(crate) fn rgb_to_linear(rgb: [u8; 3]) -> [u16; 3] {
+    let t = &tables().to_linear;
+    [t[rgb[0] as usize], t[rgb[1] as usize], t[rgb[2] as usize]]
+}
+
+/// Converts the linear light components (0 to 65535) to sRGB.
+pub(crate) fn linear_to_rgb(linear: [u32; 3]) -> [u8; 3] {
+    let t = &tables().to_srgb;
+    let idx = |v: u32| ((v.min(65535) * ((LINEAR_STEPS - 1) as u32) + 32768) >> 16) as usize;
+    [t[idx(linear[0])], t[idx(linear[1])], t[idx(linear[2])]]
+}
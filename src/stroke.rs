@@ -0,0 +1,384 @@
+use crate::geom::*;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::f32::consts::PI;
+
+/// Maximum distance (in pixels) between a curve and the line segments used to approximate it.
+pub(crate) const FLATTEN_TOLERANCE: f32 = 0.02;
+
+/// Shape used at the open ends of a stroke.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineCap {
+    /// The stroke ends exactly at the end point.
+    Butt,
+    /// The stroke ends with a half circle centered on the end point.
+    Round,
+    /// The stroke is extended by half the stroke width past the end point.
+    Square,
+}
+
+/// Shape used where two segments of a stroke meet.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum LineJoin {
+    /// Sharp corner. If the ratio between the miter length and the stroke width exceeds the
+    /// limit, then a bevel join is used instead.
+    Miter(f32),
+    Round,
+    Bevel,
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Clone, PartialEq, Debug)]
+pub struct StrokeStyle {
+    pub width: f32,
+    pub cap: LineCap,
+    pub join: LineJoin,
+    /// Alternating lengths of the dashes and gaps, starting with a dash. A pattern with an odd
+    /// number of entries is repeated to get an even number (like SVG does). If the pattern is
+    /// empty or invalid, then the stroke is solid.
+    pub dash: Vec<f32>,
+    /// Distance into the dash pattern at which the stroke starts.
+    pub dash_offset: f32,
+}
+impl StrokeStyle {
+    /// Creates a stroke style with butt caps and miter joins (miter limit of 4).
+    pub fn new(width: f32) -> Self {
+        Self {
+            width,
+            cap: LineCap::Butt,
+            join: LineJoin::Miter(4.0),
+            dash: Vec::new(),
+            dash_offset: 0.0,
+        }
+    }
+    pub fn with_width(mut self, width: f32) -> Self {
+        self.width = width;
+        self
+    }
+    pub fn with_cap(mut self, cap: LineCap) -> Self {
+        self.cap = cap;
+        self
+    }
+    pub fn with_join(mut self, join: LineJoin) -> Self {
+        self.join = join;
+        self
+    }
+    pub fn with_dash(mut self, pattern: &[f32], offset: f32) -> Self {
+        self.dash = pattern.to_vec();
+        self.dash_offset = offset;
+        self
+    }
+}
+
+/// Upper limit for the number of dashes of a single contour, a stroke that would need more
+/// dashes is drawn solid instead.
+const MAX_DASHES: f32 = 1_000_000.0;
+
+/// Converts the stroke of the polyline into a set of polygons. The stroke of every (dash of the)
+/// polyline is traced as a single outline, so its caps and joins do not overlap the segments
+/// (overlapping parts would add up their coverage at anti-aliased edges). Outer contours are
+/// oriented positively and holes negatively, so the result is filled using the non-zero rule.
+pub(crate) fn stroke_outline(
+    points: &[[f32; 2]],
+    closed: bool,
+    style: &StrokeStyle,
+) -> Vec<Vec<[f32; 2]>> {
+    let dashes =
+        dash_pattern(style).and_then(|p| split_dashes(points, closed, &p, style.dash_offset));
+    if let Some(dashes) = dashes {
+        let mut result = Vec::new();
+        for dash in dashes.iter() {
+            result.extend(stroke_solid(dash, false, style));
+        }
+        result
+    } else {
+        stroke_solid(points, closed, style)
+    }
+}
+
+fn stroke_solid(points: &[[f32; 2]], closed: bool, style: &StrokeStyle) -> Vec<Vec<[f32; 2]>> {
+    let mut result = Vec::new();
+    let hw = 0.5 * style.width;
+    if !(hw > 0.0 && hw.is_finite()) {
+        return result;
+    }
+
+    // Remove repeated points, they do not have a direction
+    let mut pts: Vec<[f32; 2]> = Vec::with_capacity(points.len());
+    for p in points.iter().cloned() {
+        if is_finite(p) && pts.last().map(|last| *last != p).unwrap_or(true) {
+            pts.push(p);
+        }
+    }
+    if closed && pts.len() > 1 && pts[0] == pts[pts.len() - 1] {
+        pts.pop();
+    }
+
+    let len = pts.len();
+    if len == 0 {
+        return result;
+    } else if len == 1 {
+        // Zero-length stroke, only the caps are visible
+        let p = pts[0];
+        match style.cap {
+            LineCap::Butt => {}
+            LineCap::Round => result.push(circle_points(p, hw)),
+            LineCap::Square => result.push(vec![
+                [p[0] - hw, p[1] - hw],
+                [p[0] + hw, p[1] - hw],
+                [p[0] + hw, p[1] + hw],
+                [p[0] - hw, p[1] + hw],
+            ]),
+        }
+        return result;
+    }
+
+    // Trace the left and right sides of the stroke. The inner side of a join passes through the
+    // point itself, so the outline is the union of the segments without overlapping joins or caps.
+    let seg_count = if closed { len } else { len - 1 };
+    let normals: Vec<[f32; 2]> = (0..seg_count)
+        .map(|i| perp(normalize(sub(pts[(i + 1) % len], pts[i]))))
+        .collect();
+    let trace_side = |side: f32| {
+        let mut out = Vec::new();
+        if closed {
+            for i in 0..len {
+                let n0 = normals[(i + len - 1) % len];
+                add_join(&mut out, pts[i], n0, normals[i], hw, side, style.join);
+            }
+        } else {
+            out.push(add(pts[0], mul(normals[0], side * hw)));
+            for i in 1..(len - 1) {
+                add_join(
+                    &mut out,
+                    pts[i],
+                    normals[i - 1],
+                    normals[i],
+                    hw,
+                    side,
+                    style.join,
+                );
+            }
+            out.push(add(pts[len - 1], mul(normals[len - 2], side * hw)));
+        }
+        out
+    };
+    let left = trace_side(1.0);
+    let mut right = trace_side(-1.0);
+    right.reverse();
+
+    if closed {
+        // The outer side is oriented positively and the inner side negatively (a hole)
+        let (mut outer, mut inner) = if signed_area(&left).abs() >= signed_area(&right).abs() {
+            (left, right)
+        } else {
+            (right, left)
+        };
+        if signed_area(&outer) < 0.0 {
+            outer.reverse();
+            inner.reverse();
+        }
+        result.push(outer);
+        result.push(inner);
+    } else {
+        let mut outline = left;
+        add_cap(
+            &mut outline,
+            pts[len - 1],
+            sub(pts[len - 1], pts[len - 2]),
+            hw,
+            style.cap,
+        );
+        outline.extend(right);
+        add_cap(&mut outline, pts[0], sub(pts[0], pts[1]), hw, style.cap);
+        if signed_area(&outline) < 0.0 {
+            outline.reverse();
+        }
+        result.push(outline);
+    }
+    result
+}
+
+/// Returns the dash pattern with an even number of entries, or None if the stroke is solid.
+fn dash_pattern(style: &StrokeStyle) -> Option<Vec<f32>> {
+    let dash = &style.dash;
+    if dash.is_empty() || dash.iter().any(|v| !(v.is_finite() && *v >= 0.0)) {
+        return None;
+    }
+    let mut pattern = dash.clone();
+    if pattern.len() % 2 == 1 {
+        pattern.extend_from_slice(dash);
+    }
+    let total: f32 = pattern.iter().sum();
+    let gaps: f32 = pattern.iter().skip(1).step_by(2).sum();
+    if total <= 0.0 || gaps <= 0.0 || !style.dash_offset.is_finite() {
+        return None;
+    }
+    Some(pattern)
+}
+
+/// Splits the polyline into the open polylines of the individual dashes. The pattern continues
+/// across the corners, so a dash can contain several points. Returns None if the polyline is not
+/// interrupted by any gap.
+fn split_dashes(
+    points: &[[f32; 2]],
+    closed: bool,
+    pattern: &[f32],
+    offset: f32,
+) -> Option<Vec<Vec<[f32; 2]>>> {
+    let mut pts: Vec<[f32; 2]> = points.iter().cloned().filter(|p| is_finite(*p)).collect();
+    if pts.len() < 2 {
+        return None;
+    }
+    if closed {
+        pts.push(pts[0]);
+    }
+    let total: f32 = pattern.iter().sum();
+    let contour_len: f32 = pts.windows(2).map(|w| length(sub(w[1], w[0]))).sum();
+    if contour_len / total * (pattern.len() as f32) > MAX_DASHES {
+        return None;
+    }
+
+    // Find the position within the pattern at the start of the contour
+    let mut idx = 0;
+    let mut remaining = offset.rem_euclid(total);
+    for _ in 0..pattern.len() {
+        if remaining <= 0.0 || remaining < pattern[idx] {
+            break;
+        }
+        remaining -= pattern[idx];
+        idx = (idx + 1) % pattern.len();
+    }
+    remaining = (pattern[idx] - remaining).max(0.0);
+
+    let starts_on = idx % 2 == 0;
+    let mut on = starts_on;
+    let mut toggled = false;
+    let mut result = Vec::new();
+    let mut cur = if on { vec![pts[0]] } else { Vec::new() };
+    for w in pts.windows(2) {
+        let (a, b) = (w[0], w[1]);
+        let seg_len = length(sub(b, a));
+        let mut t = 0.0;
+        while seg_len - t > remaining {
+            t += remaining;
+            let p = add(a, mul(sub(b, a), t / seg_len));
+            cur.push(p);
+            if on {
+                result.push(std::mem::take(&mut cur));
+            }
+            on = !on;
+            toggled = true;
+            idx = (idx + 1) % pattern.len();
+            remaining = pattern[idx];
+        }
+        remaining -= seg_len - t;
+        if on {
+            cur.push(b);
+        }
+    }
+    if !toggled && on {
+        return None;
+    }
+    if on {
+        if closed && starts_on && !result.is_empty() {
+            // The last dash continues into the first dash at the starting point
+            let first = result.remove(0);
+            cur.extend_from_slice(&first[1..]);
+        }
+        result.push(cur);
+    }
+    Some(result)
+}
+
+/// Adds the points of one side of the stroke at the join between the segments with the
+/// (unit) left normals `n0` and `n1`. The side is 1 for the left side and -1 for the right side.
+fn add_join(
+    out: &mut Vec<[f32; 2]>,
+    p: [f32; 2],
+    n0: [f32; 2],
+    n1: [f32; 2],
+    hw: f32,
+    side: f32,
+    join: LineJoin,
+) {
+    let n0 = mul(n0, side);
+    let n1 = mul(n1, side);
+    let p0 = add(p, mul(n0, hw));
+    let p1 = add(p, mul(n1, hw));
+    let turn = cross(n0, n1);
+    if turn.abs() <= 1e-6 && dot(n0, n1) > 0.0 {
+        // Straight continuation, the segments already connect
+        out.push(p1);
+        return;
+    }
+
+    out.push(p0);
+    // The side is on the inside of the turn if its normal turns towards the segments
+    if turn * side > 0.0 && turn.abs() > 1e-6 {
+        out.push(p);
+    } else {
+        match join {
+            LineJoin::Bevel => {}
+            LineJoin::Round => add_arc(out, p, n0, turn.atan2(dot(n0, n1)), hw),
+            LineJoin::Miter(limit) => {
+                // Ratio between the miter length and the stroke width is 1 / cos(a / 2), where a
+                // is the angle between the two normals
+                let cos_half = ((1.0 + dot(n0, n1)) * 0.5).max(0.0).sqrt();
+                if cos_half > 0.0 && 1.0 / cos_half <= limit {
+                    let dir = normalize(add(n0, n1));
+                    out.push(add(p, mul(dir, hw / cos_half)));
+                }
+            }
+        }
+    }
+    out.push(p1);
+}
+
+/// Adds the points of the cap between the two sides of the stroke. The outline arrives at the
+/// side given by the left normal of `outward` and continues at the opposite side.
+fn add_cap(out: &mut Vec<[f32; 2]>, p: [f32; 2], outward: [f32; 2], hw: f32, cap: LineCap) {
+    let d = normalize(outward);
+    let n = mul(perp(d), hw);
+    match cap {
+        LineCap::Butt => {}
+        LineCap::Round => add_arc(out, p, perp(d), -PI, hw),
+        LineCap::Square => {
+            let e = mul(d, hw);
+            out.push(add(add(p, n), e));
+            out.push(add(sub(p, n), e));
+        }
+    }
+}
+
+/// Adds the points of an arc around `center`, starting at the direction `start` (unit vector)
+/// and turning by `sweep` radians. The end points of the arc are not added.
+fn add_arc(out: &mut Vec<[f32; 2]>, center: [f32; 2], start: [f32; 2], sweep: f32, radius: f32) {
+    let n = ((circle_segments(radius) as f32) * sweep.abs() / (2.0 * PI)).ceil() as usize;
+    let a0 = start[1].atan2(start[0]);
+    for i in 1..n {
+        let a = a0 + sweep * (i as f32) / (n as f32);
+        out.push([center[0] + radius * a.cos(), center[1] + radius * a.sin()]);
+    }
+}
+
+/// Number of segments needed to approximate a full circle of the specified radius.
+pub(crate) fn circle_segments(radius: f32) -> usize {
+    if radius <= FLATTEN_TOLERANCE {
+        return 8;
+    }
+    let step = 2.0 * (1.0 - FLATTEN_TOLERANCE / radius).acos();
+    ((2.0 * PI / step).ceil() as usize).clamp(8, 4096)
+}
+
+pub(crate) fn circle_points(center: [f32; 2], radius: f32) -> Vec<[f32; 2]> {
+    let n = circle_segments(radius);
+    (0..n)
+        .map(|i| {
+            let a = (i as f32) * 2.0 * PI / (n as f32);
+            [center[0] + radius * a.cos(), center[1] + radius * a.sin()]
+        })
+        .collect()
+}
@@ -0,0 +1,97 @@
+use draw::*;
+
+const WHITE: Rgba = Rgba([255, 255, 255, 255]);
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba = Rgba([0, 0, 255, 255]);
+
+fn left_half_mask() -> Mask {
+    let mut mask = Mask::new_with_value(Rect::new([0, 0], [5, 10]), 255);
+    mask.try_set([4, 4], 128);
+    mask
+}
+
+#[test]
+fn clip_mask_fill_test() {
+    let mut img = Image::new_with_color([10, 10], WHITE);
+    {
+        let mut c = img.as_canvas().with_clip_mask(&left_half_mask());
+        c.fill(RED);
+        c.draw_line([0.5, 8.5], [9.5, 8.5], LineMode::Aliased, BLUE);
+        c.fill_circle([5.0, 2.0], 2.0, BLUE);
+        assert!(!c.try_set_color([20, 0], BLUE));
+        assert!(c.try_get_color_mut([7, 0]).is_none());
+        assert!(c.try_get_color_mut([1, 0]).is_some());
+    }
+    assert!(img.get([0, 0]) == RED);
+    assert!(img.get([6, 0]) == WHITE);
+    assert!(img.get([0, 8]) == BLUE);
+    assert!(img.get([7, 8]) == WHITE);
+    assert!(img.get([6, 2]) == WHITE);
+    assert!(img.get([4, 2]) == BLUE);
+
+    // Partial coverage interpolates between the old and the new color
+    let [r, g, b, a] = img.get([4, 4]).rgba();
+    assert!(r == 255 && a == 255);
+    assert!((126..=128).contains(&g) && g == b);
+
+    // Sub-canvases share the clip mask of their parent
+    let mut img = Image::new_with_color([10, 10], WHITE);
+    {
+        let mut c = img.as_canvas().with_clip_mask(&left_half_mask());
+        let mut sc = c.sub_canvas([3, 0], [7, 10]).unwrap();
+        assert!(sc.clip_mask().unwrap().rect() == Rect::new([0, 0], [10, 10]));
+        assert!(sc.clip_mask().unwrap().get([3, 0]) == 255);
+        sc.clear(BLUE);
+        sc.fill_rect([0, 1], [10, 1], RED);
+    }
+    assert!(img.get([2, 0]) == WHITE);
+    assert!(img.get([3, 0]) == BLUE);
+    assert!(img.get([5, 0]) == WHITE);
+    assert!(img.get([2, 1]) == WHITE);
+    assert!(img.get([4, 1]) == RED);
+    assert!(img.get([5, 1]) == WHITE);
+}
+
+#[test]
+fn clip_mask_image_test() {
+    let src = Image::new_with_color([10, 10], RED);
+    let src_rect = Rect::new([0, 0], src.dim());
+    let mut img = Image::new_with_color([10, 10], WHITE);
+    img.as_canvas()
+        .with_clip_mask(&left_half_mask())
+        .draw_image(&src, [0, 0]);
+    assert!(img.get([0, 0]) == RED);
+    assert!(img.get([5, 0]) == WHITE);
+    assert!(img.get([4, 4]) != RED && img.get([4, 4]) != WHITE);
+
+    let mut img = Image::new_with_color([10, 10], WHITE);
+    img.as_canvas()
+        .with_clip_mask(&left_half_mask())
+        .draw_image_region_scaled(&src, src_rect, src_rect, SampleFilter::Bilinear);
+    assert!(img.get([0, 0]) == RED);
+    assert!(img.get([5, 0]) == WHITE);
+}
+
+#[test]
+fn clip_mask_stack_test() {
+    let mut img = Image::new_with_color([10, 10], WHITE);
+    {
+        let mut c = img.as_canvas();
+        c.push_clip_mask(&left_half_mask());
+        c.push_clip_mask(&Mask::new_with_value(Rect::new([0, 5], [10, 5]), 255));
+        assert!(c.clip_mask().unwrap().get([0, 0]) == 0);
+        c.fill(RED);
+        assert!(c.pop_clip_mask());
+        c.fill_rect([0, 0], [10, 2], BLUE);
+        assert!(c.pop_clip_mask());
+        assert!(!c.pop_clip_mask());
+        assert!(c.clip_mask().is_none());
+        c.fill_rect([9, 9], [1, 1], BLUE);
+    }
+    assert!(img.get([0, 5]) == RED);
+    assert!(img.get([0, 4]) == WHITE);
+    assert!(img.get([6, 5]) == WHITE);
+    assert!(img.get([0, 0]) == BLUE);
+    assert!(img.get([6, 0]) == WHITE);
+    assert!(img.get([9, 9]) == BLUE);
+}
@@ -0,0 +1,86 @@
+use draw::*;
+
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+
+#[test]
+fn damage_tracker_test() {
+    let tracker = DamageTracker::new();
+    let mut img = Image::new([100, 100]);
+    let icon = Image::new_with_color([4, 4], RED);
+    {
+        let mut c = img.as_canvas().with_damage_tracker(&tracker);
+        c.fill_rect([10, 10], [5, 5], RED);
+        c.fill_circle([50.0, 50.0], 4.0, RED);
+        c.draw_image(&icon, [98, 0]);
+        c.nth_row_mut(90)[0] = RED;
+    }
+    let mut rects = tracker.take();
+    rects.sort_by_key(|r| r.pos);
+    assert!(rects[0] == Rect::new([10, 10], [5, 5]));
+    assert!(rects[1] == Rect::new([46, 46], [8, 8]));
+    assert!(rects[2] == Rect::new([98, 0], [2, 4]));
+    assert!(rects.len() == 3 && tracker.is_empty());
+
+    // Sub-canvases and pixels share the tracker, changes outside of the canvas are ignored
+    {
+        let mut c = img.as_canvas().with_damage_tracker(&tracker);
+        let mut sc = c.sub_canvas([20, 20], [10, 10]).unwrap();
+        sc.draw_line([0.5, 25.5], [50.5, 25.5], LineMode::Aliased, RED);
+        c.try_set_color([0, 99], RED);
+        c.try_set_color([200, 0], RED);
+        *c.try_get_color_mut([99, 99]).unwrap() = RED;
+    }
+    let mut rects = tracker.rects();
+    rects.sort_by_key(|r| r.pos);
+    assert!(
+        rects
+            == vec![
+                Rect::new([0, 99], [1, 1]),
+                Rect::new([20, 24], [10, 3]),
+                Rect::new([99, 99], [1, 1])
+            ]
+    );
+    assert!(tracker.bounding_rect() == Some(Rect::new([0, 24], [100, 76])));
+    tracker.reset();
+    assert!(tracker.bounding_rect().is_none());
+}
+
+#[test]
+fn damage_merge_test() {
+    let tracker = DamageTracker::new().with_max_rects(2);
+    tracker.add(Rect::new([0, 0], [10, 10]));
+    tracker.add(Rect::new([2, 2], [3, 3]));
+    tracker.add(Rect::new([5, 5], [10, 10]));
+    assert!(tracker.rects() == vec![Rect::new([0, 0], [15, 15])]);
+
+    // Once the limit is reached, the rectangle that grows the least is used
+    tracker.add(Rect::new([50, 0], [5, 5]));
+    tracker.add(Rect::new([16, 0], [2, 2]));
+    let mut rects = tracker.rects();
+    rects.sort_by_key(|r| r.pos);
+    assert!(rects == vec![Rect::new([0, 0], [18, 15]), Rect::new([50, 0], [5, 5])]);
+
+    let tracker = DamageTracker::new().with_max_rects(1);
+    tracker.add(Rect::new([0, 0], [1, 1]));
+    tracker.add(Rect::new([9, 9], [1, 1]));
+    assert!(tracker.rects() == vec![Rect::new([0, 0], [10, 10])]);
+}
+
+#[test]
+fn damage_text_test() {
+    let font_cache =
+        FontCache::ttf_from_static(include_bytes!("../examples/carlito/Carlito-Regular.ttf"))
+            .unwrap();
+    let mut font = font_cache.font();
+    let tracker = DamageTracker::new().with_max_rects(1000);
+    let mut img = Image::new([200, 50]);
+    img.as_canvas()
+        .with_damage_tracker(&tracker)
+        .draw_text(&mut font, 20, RED, "Damage", [10, 10], None);
+
+    // Text records one rectangle per glyph instead of one per pixel
+    let rects = tracker.rects();
+    assert!(!rects.is_empty() && rects.len() <= 6);
+    let bounds = font.render_bounds(20, "Damage", [10, 10], None);
+    assert!(tracker.bounding_rect() == bounds);
+}
@@ -0,0 +1,147 @@
+use draw::*;
+
+const WHITE: Rgba = Rgba([255, 255, 255, 255]);
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba = Rgba([0, 0, 255, 200]);
+
+fn badge(list: &mut DisplayList, icon: &Image, label: &str) {
+    list.fill_rect([0, 0], [40, 20], RED);
+    list.draw_rect([0, 0], [40, 20], BLUE);
+    list.fill_circle([30.0, 10.0], 5.0, BLUE);
+    list.fill_rounded_rect_using(
+        BlendMode::Mix(MixBlendMode::Multiply),
+        Rect::new([2, 12], [10, 6]),
+        [2.0; 4],
+        BLUE,
+    );
+    list.draw_line([0.5, 19.5], [39.5, 0.5], LineMode::AntiAliased, WHITE);
+    list.draw_image_using(BlendMode::PorterDuff(PorterDuff::Xor), icon, [4, 4]);
+    list.draw_text(0, 10, WHITE, label, [12, 2], None);
+}
+
+#[test]
+fn display_list_replay_test() {
+    let font_cache =
+        FontCache::ttf_from_static(include_bytes!("../examples/carlito/Carlito-Regular.ttf"))
+            .unwrap();
+    let icon = Image::new_with_color([4, 4], Rgba([0, 255, 0, 128]));
+    let mut list = DisplayList::new();
+    badge(&mut list, &icon, "Hi");
+    badge(&mut list, &icon, "Hi");
+    assert!(list.commands().len() == 14 && list.images().len() == 1);
+
+    // Replaying at an offset matches drawing the same commands directly
+    let mut expected = Image::new_with_color([60, 40], WHITE);
+    {
+        let mut c = expected.as_canvas();
+        c.fill_rect([10, 10], [40, 20], RED);
+        c.draw_rect([10, 10], [40, 20], BLUE);
+        c.fill_circle([40.0, 20.0], 5.0, BLUE);
+        let rect = Rect::new([12, 22], [10, 6]);
+        c.fill_rounded_rect_using(MixBlendMode::Multiply, rect, [2.0; 4], BLUE);
+        c.draw_line([10.5, 29.5], [49.5, 10.5], LineMode::AntiAliased, WHITE);
+        c.draw_image_using(PorterDuff::Xor, &icon, [14, 14]);
+        c.draw_text(&mut font_cache.font(), 10, WHITE, "Hi", [22, 12], None);
+    }
+    let mut single = DisplayList::new();
+    badge(&mut single, &icon, "Hi");
+    let mut img = Image::new_with_color([60, 40], WHITE);
+    single.replay(&mut img.as_canvas(), [10, 10], &mut [font_cache.font()]);
+    assert!(img == expected);
+
+    // Text without a font is skipped
+    let mut img = Image::new_with_color([60, 40], WHITE);
+    single.replay(&mut img.as_canvas(), [10, 10], &mut []);
+    assert!(img != expected);
+    assert!(img.get([10, 10]) == expected.get([10, 10]));
+}
+
+#[test]
+fn display_list_compare_test() {
+    let icon = Image::new_with_color([4, 4], RED);
+    let (mut a, mut b) = (DisplayList::new(), DisplayList::new());
+    badge(&mut a, &icon, "One");
+    badge(&mut b, &icon, "Two");
+    assert!(a != b);
+    let changed: Vec<usize> = (0..a.commands().len())
+        .filter(|i| a.commands()[*i] != b.commands()[*i])
+        .collect();
+    assert!(changed == vec![6]);
+
+    b.clear();
+    assert!(b.is_empty() && b.images().is_empty());
+    b.push(DrawCommand::Fill {
+        color: RED,
+        mode: Some(BlendMode::Overwrite),
+    });
+    let mut img = Image::new([4, 4]);
+    b.replay(&mut img.as_canvas(), [0, 0], &mut []);
+    assert!(img.get([3, 3]) == RED);
+
+    // Identical images are stored once
+    let image = b.add_image(&icon);
+    assert!(b.add_image(&icon.clone()) == image);
+    assert!(b.add_image(&Image::new_with_color([4, 4], BLUE)) == image + 1);
+    assert!(b.images().len() == 2);
+
+    // Image indices must refer to images of the list
+    assert!(b.push(DrawCommand::DrawImage {
+        image,
+        pos: [0, 0],
+        mode: None,
+    }));
+    assert!(!b.push(DrawCommand::DrawImage {
+        image: image + 2,
+        pos: [0, 0],
+        mode: None,
+    }));
+    assert!(b.commands().len() == 2);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn display_list_serde_test() {
+    let icon = Image::new_with_color([4, 4], Rgba([0, 255, 0, 128]));
+    let mut list = DisplayList::new();
+    badge(&mut list, &icon, "Hi");
+    let mut path = Path::new();
+    path.move_to([0.0, 0.0]).line_to([10.0, 5.0]);
+    list.stroke_path(&path, &StrokeStyle::new(2.0), RED);
+    list.draw_image_region_scaled(
+        &icon,
+        Rect::new([0, 0], [2, 2]),
+        Rect::new([0, 0], [8, 8]),
+        SampleFilter::Bilinear,
+    );
+
+    let json = serde_json::to_string(&list).unwrap();
+    let mut copy: DisplayList = serde_json::from_str(&json).unwrap();
+    assert!(copy == list);
+    let (mut a, mut b) = (Image::new([60, 40]), Image::new([60, 40]));
+    list.replay(&mut a.as_canvas(), [10, 10], &mut []);
+    copy.replay(&mut b.as_canvas(), [10, 10], &mut []);
+    assert!(a == b);
+    assert!(copy.add_image(&icon) == 0 && copy.images().len() == 1);
+
+    // Deserialized commands with an invalid image index are skipped
+    let json = json.replacen("\"image\":0", "\"image\":5", 1);
+    let copy: DisplayList = serde_json::from_str(&json).unwrap();
+    assert!(copy != list);
+    let mut c = Image::new([60, 40]);
+    copy.replay(&mut c.as_canvas(), [10, 10], &mut []);
+    let mut expected = DisplayList::new();
+    expected.add_image(&icon);
+    let skipped = list
+        .commands()
+        .iter()
+        .position(|cmd| matches!(cmd, DrawCommand::DrawImage { .. }))
+        .unwrap();
+    for (i, cmd) in list.commands().iter().enumerate() {
+        if i != skipped {
+            assert!(expected.push(cmd.clone()));
+        }
+    }
+    let mut d = Image::new([60, 40]);
+    expected.replay(&mut d.as_canvas(), [10, 10], &mut []);
+    assert!(c == d && c != a);
+}
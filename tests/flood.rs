@@ -0,0 +1,78 @@
+use draw::*;
+
+#[test]
+fn flood_fill_test() {
+    let black = Rgba([0, 0, 0, 255]);
+    let red = Rgba([255, 0, 0, 255]);
+
+    // Square outline, the fill stays inside of it
+    let mut img = Image::new_with_color([10, 10], Rgba([255, 255, 255, 255]));
+    img.as_canvas().draw_rect([2, 2], [6, 6], black);
+    let mask = img
+        .flood_fill([4, 4], red, 0.0, Connectivity::Four)
+        .unwrap();
+    assert!(mask.rect() == Rect::new([3, 3], [4, 4]));
+    assert!(mask.get([3, 3]) == 255 && mask.get([2, 2]) == 0);
+    assert!(img.get([3, 3]) == red && img.get([6, 6]) == red);
+    assert!(img.get([2, 2]) == black);
+    assert!(img.get([0, 0]) == Rgba([255, 255, 255, 255]));
+
+    // The diagonal line only separates the regions with 4-connectivity
+    let mut img = Image::new([4, 4]);
+    for i in 0..4 {
+        img.set([i, 3 - i], black);
+    }
+    let mask = img
+        .flood_fill([0, 0], red, 0.0, Connectivity::Four)
+        .unwrap();
+    assert!(mask.rect() == Rect::new([0, 0], [3, 3]));
+    assert!(img.get([3, 3]).alpha() == 0);
+    let mut img = Image::new([4, 4]);
+    for i in 0..4 {
+        img.set([i, 3 - i], black);
+    }
+    let mask = img
+        .flood_fill([0, 0], red, 0.0, Connectivity::Eight)
+        .unwrap();
+    assert!(mask.rect() == Rect::new([0, 0], [4, 4]));
+    assert!(img.get([3, 3]) == red);
+
+    assert!(img
+        .flood_fill([4, 0], red, 0.0, Connectivity::Four)
+        .is_none());
+}
+
+#[test]
+fn flood_select_test() {
+    // Colors within the tolerance belong to the region
+    let mut img = Image::new([8, 1]);
+    for x in 0..8 {
+        img.set([x, 0], Rgba([(x * 10) as u8, 0, 0, 255]));
+    }
+    let params = FloodFill::new(20.0, Connectivity::Four);
+    let mask = img.flood_select([0, 0], &params).unwrap();
+    assert!(mask.rect() == Rect::new([0, 0], [3, 1]));
+
+    // The region either stops at the canvas bounds or covers the whole image
+    let mut img = Image::new([8, 8]);
+    let mut c = img.as_canvas();
+    let sc = c.sub_canvas([2, 2], [4, 4]).unwrap();
+    let params = FloodFill::new(0.0, Connectivity::Four);
+    let mask = sc.flood_select([3, 3], &params).unwrap();
+    assert!(mask.rect() == Rect::new([2, 2], [4, 4]));
+    let mask = sc
+        .flood_select([3, 3], &params.with_within_canvas(false))
+        .unwrap();
+    assert!(mask.rect() == Rect::new([0, 0], [8, 8]));
+
+    // Only the part within the canvas is drawn
+    let mut sc = c.sub_canvas([2, 2], [4, 4]).unwrap();
+    sc.flood_fill_using(
+        ColorBlendOverwrite,
+        [3, 3],
+        Rgba([0, 0, 255, 255]),
+        &params.with_within_canvas(false),
+    );
+    assert!(img.get([2, 2]).alpha() == 255);
+    assert!(img.get([1, 1]).alpha() == 0);
+}
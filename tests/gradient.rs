@@ -0,0 +1,65 @@
+use draw::*;
+
+#[test]
+fn gradient_linear_test() {
+    let red = Rgba([255, 0, 0, 255]);
+    let blue = Rgba([0, 0, 255, 255]);
+    let g = Gradient::linear([0.0, 0.0], [10.0, 0.0])
+        .with_stop(0.0, red)
+        .with_stop(1.0, blue);
+    assert!(g.color_at([-5.0, 3.0]) == red);
+    assert!(g.color_at([15.0, 3.0]) == blue);
+    assert!(g.color_at([5.0, 100.0]) == Rgba([128, 0, 128, 255]));
+
+    // Linear light mixes to a brighter midpoint
+    let lin = g.clone().with_interpolation(ColorInterpolation::LinearRgb);
+    assert!(lin.color_at([5.0, 0.0]) == Rgba([188, 0, 188, 255]));
+
+    let repeat = g.clone().with_spread(SpreadMode::Repeat);
+    assert!(repeat.color_at([12.5, 0.0]) == g.color_at([2.5, 0.0]));
+    let reflect = g.clone().with_spread(SpreadMode::Reflect);
+    assert!(reflect.color_at([12.5, 0.0]) == g.color_at([7.5, 0.0]));
+
+    // Pixels are sampled at their center
+    let mut img = Image::new([10, 2]);
+    img.as_canvas().fill(&g);
+    assert!(img.get([0, 0]) == g.color_at([0.5, 0.5]));
+    assert!(img.get([9, 1]) == g.color_at([9.5, 1.5]));
+}
+
+#[test]
+fn gradient_shapes_test() {
+    let black = Rgba([0, 0, 0, 255]);
+    let white = Rgba([255, 255, 255, 255]);
+
+    let radial = Gradient::radial([10.0, 10.0], 10.0)
+        .with_stop(0.0, black)
+        .with_stop(1.0, white);
+    assert!(radial.color_at([10.0, 10.0]) == black);
+    assert!(radial.color_at([10.0, 15.0]) == Rgba([128, 128, 128, 255]));
+    assert!(radial.color_at([25.0, 10.0]) == white);
+
+    // Hard stops: black on the right half (clockwise from the x-axis), white on the left half
+    let conic = Gradient::conic([10.0, 10.0], 0.0)
+        .with_stop(0.0, black)
+        .with_stop(0.5, black)
+        .with_stop(0.5, white)
+        .with_stop(1.0, white);
+    assert!(conic.color_at([10.0, 15.0]) == black);
+    assert!(conic.color_at([10.0, 5.0]) == white);
+
+    // Transparent stops do not darken the gradient
+    let fade = Gradient::linear([0.0, 0.0], [10.0, 0.0])
+        .with_stop(0.0, white)
+        .with_stop(1.0, Rgba([0, 0, 0, 0]));
+    assert!(fade.color_at([5.0, 0.0]) == Rgba([255, 255, 255, 128]));
+
+    // Only the inside of the path is painted
+    let mut p = Path::new();
+    p.rounded_rect([2.0, 2.0], [16.0, 16.0], [4.0; 4]);
+    let mut img = Image::new([20, 20]);
+    img.as_canvas()
+        .fill_path(&p, FillRule::NonZero, &radial);
+    assert!(img.get([0, 0]).alpha() == 0);
+    assert!(img.get([10, 10]) == radial.color_at([10.5, 10.5]));
+}
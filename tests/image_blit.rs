@@ -0,0 +1,79 @@
+use draw::*;
+
+fn pattern_image(dim: [u32; 2], seed: u32) -> Image {
+    let mut img = Image::new(dim);
+    let mut v = seed;
+    for c in img.buffer_mut().iter_mut() {
+        v = v.wrapping_mul(1_103_515_245).wrapping_add(12345);
+        *c = Rgba::from_rgba_u32(v);
+    }
+    img
+}
+
+// Per-pixel reference implementation of the image drawing
+fn draw_reference<Mode: ImageBlendMode>(
+    c: &mut Canvas,
+    mode: Mode,
+    img: &Image,
+    src_rect: Rect,
+    pos: [i32; 2],
+) {
+    for y in 0..(src_rect.height() as i32) {
+        for x in 0..(src_rect.width() as i32) {
+            let src = [src_rect.x() + x, src_rect.y() + y];
+            if let Some(color) = img.try_get(src) {
+                if let Some(bg) = c.try_get_color_mut([pos[0] + x, pos[1] + y]) {
+                    mode.blend_color(bg, color);
+                }
+            }
+        }
+    }
+}
+
+fn check_mode<Mode: ImageBlendMode>(mode: Mode) {
+    let src = pattern_image([13, 9], 7);
+    let bg = pattern_image([20, 16], 3);
+    let rects = [
+        Rect::new([0, 0], [13, 9]),
+        Rect::new([3, 2], [6, 5]),
+        Rect::new([-4, -3], [30, 30]),
+    ];
+    let positions = [[0, 0], [5, 4], [-6, -2], [15, 12], [30, 2], [2, -20]];
+    for rect in rects.iter() {
+        for pos in positions.iter() {
+            let mut expected = bg.clone();
+            let mut actual = bg.clone();
+            {
+                let mut c = expected.as_canvas();
+                let mut c = c.sub_canvas([2, 1], [15, 13]).unwrap();
+                draw_reference(&mut c, mode, &src, *rect, *pos);
+            }
+            {
+                let mut c = actual.as_canvas();
+                let mut c = c.sub_canvas([2, 1], [15, 13]).unwrap();
+                c.draw_image_region_using(mode, &src, *rect, *pos);
+            }
+            assert!(actual == expected);
+        }
+    }
+}
+
+#[test]
+fn image_blit_test() {
+    check_mode(ImageBlendOverwrite);
+    check_mode(ImageBlendOpaque);
+    check_mode(ImageBlendTransparent);
+    check_mode(ImageBlendTinted::new(
+        ImageBlendTransparent,
+        Rgba([255, 128, 0, 200]),
+    ));
+}
+
+#[test]
+fn image_blit_full_test() {
+    let src = pattern_image([8, 8], 11);
+    let mut img = Image::new([8, 8]);
+    img.as_canvas()
+        .draw_image_using(ImageBlendOverwrite, &src, [0, 0]);
+    assert!(img == src);
+}
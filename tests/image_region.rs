@@ -0,0 +1,65 @@
+use draw::*;
+
+fn sprite_sheet() -> Image {
+    // Two 4x4 sprites next to each other, red on the left and blue on the right
+    let mut img = Image::new([8, 4]);
+    img.as_canvas()
+        .fill_rect([0, 0], [4, 4], Rgba([255, 0, 0, 255]));
+    img.as_canvas()
+        .fill_rect([4, 0], [4, 4], Rgba([0, 0, 255, 255]));
+    img.set([5, 1], Rgba([0, 255, 0, 255]));
+    img
+}
+
+#[test]
+fn image_region_test() {
+    let sheet = sprite_sheet();
+
+    let mut img = Image::new([10, 10]);
+    img.as_canvas()
+        .draw_image_region(&sheet, Rect::new([4, 0], [4, 4]), [3, 2]);
+    let mut expected = Image::new([10, 10]);
+    expected
+        .as_canvas()
+        .draw_image(&sheet.sub_image([4, 0], [4, 4]), [3, 2]);
+    assert!(img == expected);
+
+    // Parts of the source rectangle outside of the image are skipped
+    let mut img = Image::new([10, 10]);
+    img.as_canvas()
+        .draw_image_region(&sheet, Rect::new([6, -2], [4, 4]), [0, 0]);
+    assert!(img.get([0, 2]) == sheet.get([6, 0]));
+    assert!(img.get([1, 3]) == sheet.get([7, 1]));
+    assert!(img.get([0, 1]).alpha() == 0);
+    assert!(img.get([2, 2]).alpha() == 0);
+}
+
+#[test]
+fn image_region_scaled_test() {
+    let sheet = sprite_sheet();
+
+    let mut img = Image::new([8, 8]);
+    img.as_canvas().draw_image_region_scaled(
+        &sheet,
+        Rect::new([4, 0], [4, 4]),
+        Rect::new([0, 0], [8, 8]),
+        SampleFilter::Nearest,
+    );
+    assert!(img.get([2, 2]) == Rgba([0, 255, 0, 255]));
+    assert!(img.get([3, 3]) == Rgba([0, 255, 0, 255]));
+    assert!(img.get([4, 4]) == Rgba([0, 0, 255, 255]));
+
+    // Bilinear sampling does not pick up the colors of the neighboring sprite
+    let mut img = Image::new([8, 8]);
+    img.as_canvas().draw_image_region_scaled(
+        &sheet,
+        Rect::new([0, 0], [4, 4]),
+        Rect::new([0, 0], [8, 8]),
+        SampleFilter::Bilinear,
+    );
+    for y in 0..8 {
+        for x in 0..8 {
+            assert!(img.get([x, y]).rgb() == [255, 0, 0]);
+        }
+    }
+}
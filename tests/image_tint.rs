@@ -0,0 +1,43 @@
+use draw::*;
+
+fn icon() -> Image {
+    let mut img = Image::new_with_color([4, 4], Rgba([255, 255, 255, 255]));
+    img.set([1, 1], Rgba([200, 100, 50, 128]));
+    img
+}
+
+#[test]
+fn image_tint_test() {
+    // A white tint with full opacity leaves the image unchanged
+    let mut a = Image::new_with_color([8, 8], Rgba([10, 20, 30, 255]));
+    let mut b = a.clone();
+    a.as_canvas().draw_image(&icon(), [2, 2]);
+    b.as_canvas()
+        .draw_image_tinted(&icon(), [2, 2], Rgba([255, 255, 255, 255]), 1.0);
+    assert!(a == b);
+
+    let mut img = Image::new([4, 4]);
+    let mode = ImageBlendTinted::new(ImageBlendOverwrite, Rgba([255, 0, 128, 255]));
+    img.as_canvas().draw_image_using(mode, &icon(), [0, 0]);
+    assert!(img.get([0, 0]) == Rgba([255, 0, 128, 255]));
+    assert!(img.get([1, 1]) == Rgba([200, 0, 25, 128]));
+
+    let mode = mode.with_opacity(0.5);
+    assert!(mode.opacity() == 0.5);
+    img.as_canvas().draw_image_using(mode, &icon(), [0, 0]);
+    assert!(img.get([0, 0]) == Rgba([255, 0, 128, 128]));
+    assert!(img.get([1, 1]) == Rgba([200, 0, 25, 64]));
+}
+
+#[test]
+fn image_opacity_test() {
+    let mut img = Image::new_with_color([4, 4], Rgba([0, 0, 0, 255]));
+    img.as_canvas()
+        .draw_image_tinted(&icon(), [0, 0], Rgba([255, 255, 255, 255]), 0.0);
+    assert!(img.buffer().iter().all(|c| *c == Rgba([0, 0, 0, 255])));
+
+    img.as_canvas()
+        .draw_image_tinted(&icon(), [0, 0], Rgba([255, 255, 255, 255]), 0.5);
+    let c = img.get([0, 0]);
+    assert!(c.alpha() == 255 && c.red() >= 127 && c.red() <= 129);
+}
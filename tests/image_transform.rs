@@ -0,0 +1,77 @@
+use draw::*;
+use std::f32::consts::PI;
+
+fn test_image() -> Image {
+    let mut img = Image::new([4, 3]);
+    for y in 0..3 {
+        for x in 0..4 {
+            img.set([x, y], Rgba([(x * 60) as u8, (y * 100) as u8, 50, 255]));
+        }
+    }
+    img
+}
+
+#[test]
+fn affine_test() {
+    let t = Affine::translate(3.0, -2.0)
+        .then_scale(2.0, 0.5)
+        .then(Affine::rotate_around(0.3, [1.0, 2.0]));
+    let inv = t.invert().unwrap();
+    let p = inv.transform_point(t.transform_point([5.0, 7.0]));
+    assert!((p[0] - 5.0).abs() < 1e-4 && (p[1] - 7.0).abs() < 1e-4);
+
+    // Rotations are clockwise on screen (the y-axis points down)
+    let p = Affine::rotate(0.5 * PI).transform_point([1.0, 0.0]);
+    assert!(p[0].abs() < 1e-6 && (p[1] - 1.0).abs() < 1e-6);
+
+    assert!(Affine::scale(0.0, 1.0).invert().is_none());
+}
+
+#[test]
+fn image_transform_test() {
+    let src = test_image();
+
+    // Integer translations copy the pixels for every filter
+    for filter in [
+        SampleFilter::Nearest,
+        SampleFilter::Bilinear,
+        SampleFilter::Bicubic,
+    ]
+    .iter()
+    {
+        let mut img = Image::new([8, 8]);
+        img.as_canvas()
+            .draw_image_transformed(&src, Affine::translate(2.0, 3.0), *filter);
+        let mut expected = Image::new([8, 8]);
+        expected.as_canvas().draw_image(&src, [2, 3]);
+        assert!(img == expected);
+    }
+
+    // Scaling by two with nearest sampling duplicates every pixel
+    let mut img = Image::new([8, 6]);
+    img.as_canvas()
+        .draw_image_transformed(&src, Affine::scale(2.0, 2.0), SampleFilter::Nearest);
+    for y in 0..6 {
+        for x in 0..8 {
+            assert!(img.get([x, y]) == src.get([x / 2, y / 2]));
+        }
+    }
+
+    // A quarter turn moves the top-left pixel to the top-right corner
+    let t = Affine::rotate(0.5 * PI).then_translate(3.0, 0.0);
+    let mut img = Image::new([3, 4]);
+    img.as_canvas()
+        .draw_image_transformed(&src, t, SampleFilter::Nearest);
+    assert!(img.get([2, 0]) == src.get([0, 0]));
+    assert!(img.get([0, 0]) == src.get([0, 2]));
+    assert!(img.get([0, 3]) == src.get([3, 2]));
+
+    // Bilinear sampling halfway between two pixels mixes them
+    let mut img = Image::new([8, 3]);
+    img.as_canvas().draw_image_transformed(
+        &src,
+        Affine::translate(0.5, 0.0),
+        SampleFilter::Bilinear,
+    );
+    assert!(img.get([2, 1]) == Rgba([90, 100, 50, 255]));
+}
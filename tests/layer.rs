@@ -0,0 +1,72 @@
+use draw::*;
+
+const WHITE: Rgba = Rgba([255, 255, 255, 255]);
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba = Rgba([0, 0, 255, 255]);
+
+fn half_over_white(c: Rgba) -> Rgba {
+    let mut result = WHITE;
+    ImageBlendMode::blend_color(&ImageBlendTransparent, &mut result, c.with_alpha(128));
+    result
+}
+
+#[test]
+fn layer_opacity_test() {
+    // Overlapping shapes in a layer are faded as one unit
+    let mut img = Image::new_with_color([20, 10], WHITE);
+    img.as_canvas().draw_layer(0.5, MixBlendMode::Normal, |c| {
+        c.fill_rect([0, 0], [12, 10], RED);
+        c.fill_rect([8, 0], [12, 10], BLUE);
+    });
+    assert!(img.get([2, 2]) == half_over_white(RED));
+    assert!(img.get([10, 2]) == half_over_white(BLUE));
+    assert!(img.get([18, 2]) == half_over_white(BLUE));
+
+    // Layers of sub-canvases use the coordinate space of the image
+    let mut img = Image::new_with_color([20, 20], WHITE);
+    {
+        let mut c = img.as_canvas();
+        let mut sc = c.sub_canvas([5, 5], [10, 10]).unwrap();
+        sc.draw_layer(1.0, MixBlendMode::Multiply, |c| {
+            assert!(c.rect() == Rect::new([5, 5], [10, 10]));
+            c.fill(Rgba([128, 128, 128, 255]));
+            c.fill_rect([0, 0], [7, 7], RED);
+            let mut inner = c.sub_canvas([8, 8], [2, 2]).unwrap();
+            inner.clear(BLUE);
+            assert!(c
+                .flood_fill([12, 12], RED, 0.0, Connectivity::Four)
+                .is_some());
+        });
+    }
+    assert!(img.get([4, 4]) == WHITE);
+    assert!(img.get([6, 6]) == RED);
+    assert!(img.get([8, 8]) == BLUE);
+    assert!(img.get([9, 9]) == BLUE);
+    assert!(img.get([10, 10]) == RED);
+    assert!(img.get([15, 15]) == WHITE);
+}
+
+#[test]
+fn layer_stack_test() {
+    let mut img = Image::new_with_color([4, 4], WHITE);
+    {
+        let mut c = img.as_canvas();
+        let mut stack = LayerStack::new(&mut c);
+        stack.push_layer(0.5, MixBlendMode::Normal);
+        stack.canvas().fill_rect([0, 0], [2, 4], RED);
+        stack.push_layer(1.0, MixBlendMode::Normal);
+        stack.canvas().fill_rect([1, 0], [2, 4], BLUE);
+        assert!(stack.depth() == 2);
+        assert!(stack.pop_layer());
+        // The inner layer is part of the outer layer, which is still transparent
+        assert!(stack.depth() == 1);
+        // The remaining layer is composited when the stack is dropped
+    }
+    assert!(img.get([0, 0]) == half_over_white(RED));
+    assert!(img.get([1, 0]) == half_over_white(BLUE));
+    assert!(img.get([3, 0]) == WHITE);
+
+    let mut c = img.as_canvas();
+    let mut stack = LayerStack::new(&mut c);
+    assert!(!stack.pop_layer());
+}
@@ -0,0 +1,47 @@
+use draw::*;
+
+#[test]
+fn aliased_line_test() {
+    let mut img = Image::new([8, 8]);
+    let red = Rgba([255, 0, 0, 255]);
+    img.as_canvas()
+        .draw_line([0.5, 0.5], [7.5, 7.5], LineMode::Aliased, red);
+    for y in 0..8 {
+        for x in 0..8 {
+            let expected = if x == y { red } else { Rgba([0, 0, 0, 0]) };
+            assert!(img.get([x, y]) == expected);
+        }
+    }
+}
+
+#[test]
+fn antialiased_line_test() {
+    let mut img = Image::new([8, 8]);
+    let c = Rgba([0, 0, 255, 255]);
+    // Halfway between the pixel centers of row 2 and row 3
+    img.as_canvas()
+        .draw_line([-100.0, 3.0], [100.0, 3.0], LineMode::AntiAliased, c);
+    for x in 0..8 {
+        assert!(img.get([x, 1]).alpha() == 0);
+        assert!(img.get([x, 2]).alpha() == 128);
+        assert!(img.get([x, 3]).alpha() == 128);
+        assert!(img.get([x, 4]).alpha() == 0);
+    }
+}
+
+#[test]
+fn line_clipping_test() {
+    let mut img = Image::new([16, 16]);
+    let c = Rgba([0, 255, 0, 255]);
+    let mut canvas = img.as_canvas();
+    if let Some(mut sc) = canvas.sub_canvas([4, 4], [8, 8]) {
+        sc.draw_line([-1e9, 8.5], [1e9, 8.5], LineMode::Aliased, c);
+        sc.draw_line([8.5, -1e9], [8.5, 1e9], LineMode::AntiAliased, c);
+    }
+    assert!(img.get([3, 8]).alpha() == 0);
+    assert!(img.get([4, 8]) == c);
+    assert!(img.get([11, 8]) == c);
+    assert!(img.get([12, 8]).alpha() == 0);
+    assert!(img.get([8, 3]).alpha() == 0);
+    assert!(img.get([8, 4]).alpha() == 255);
+}
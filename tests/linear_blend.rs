@@ -0,0 +1,79 @@
+use draw::*;
+
+#[test]
+fn linear_blend_test() {
+    // Blending a color with itself keeps the color
+    for v in 0..=255u8 {
+        let c = Rgba([v, 255 - v, v / 2, 255]);
+        let mut bg = c;
+        ImageBlendMode::blend_color(&ImageBlendLinear, &mut bg, c.with_alpha(77));
+        assert!(bg == c);
+    }
+
+    // 50% white over black is 50% linear light, which is much brighter than sRGB 128
+    let white = Rgba([255, 255, 255, 128]);
+    let mut bg = Rgba([0, 0, 0, 255]);
+    ImageBlendMode::blend_color(&ImageBlendLinear, &mut bg, white);
+    assert!(bg == Rgba([188, 188, 188, 255]));
+    let mut bg = Rgba([0, 0, 0, 255]);
+    ImageBlendMode::blend_color(&ImageBlendTransparent, &mut bg, white);
+    assert!(bg == Rgba([128, 128, 128, 255]));
+
+    // Transparent backgrounds take the color of the source
+    let mut bg = Rgba([0, 0, 0, 0]);
+    ColorBlendMode::blend_color(
+        &ColorBlendLinear,
+        &mut bg,
+        &ColorBlendMode::prepare_color(&ColorBlendLinear, Rgba([10, 20, 30, 40])),
+    );
+    assert!(bg == Rgba([10, 20, 30, 40]));
+    let mut bg = Rgba([255, 0, 0, 128]);
+    ImageBlendMode::blend_color(&ImageBlendLinear, &mut bg, Rgba([0, 0, 255, 128]));
+    assert!(bg.alpha() == 192 && bg.red() > 128 && bg.blue() > 128);
+}
+
+#[test]
+fn linear_blend_shape_test() {
+    let mut a = Image::new_with_color([20, 20], Rgba([0, 0, 0, 255]));
+    let mut b = a.clone();
+    let white = Rgba([255, 255, 255, 255]);
+    a.as_canvas().fill_circle([10.0, 10.0], 6.0, white);
+    b.as_canvas()
+        .fill_circle_using(ColorAlphaBlendLinear, [10.0, 10.0], 6.0, white);
+    assert!(a.get([10, 10]) == b.get([10, 10]));
+    // Partially covered edge pixels are brighter in linear light
+    let edge = [14, 14];
+    assert!(a.get(edge).red() > 0 && a.get(edge).red() < 255);
+    assert!(b.get(edge).red() > a.get(edge).red());
+}
+
+#[test]
+fn linear_text_test() {
+    let font_cache =
+        FontCache::ttf_from_static(include_bytes!("../examples/carlito/Carlito-Regular.ttf"))
+            .unwrap();
+    let black = Rgba([0, 0, 0, 255]);
+    let white = Rgba([255, 255, 255, 255]);
+    let mut a = Image::new_with_color([120, 40], black);
+    let mut b = a.clone();
+    a.as_canvas()
+        .draw_text(&mut font_cache.font(), 24, white, "Text", [0, 0], None);
+    b.as_canvas().draw_text_using(
+        ColorBlendLinear,
+        &mut font_cache.font(),
+        24,
+        white,
+        "Text",
+        [0, 0],
+        None,
+    );
+    // Anti-aliased pixels of white text on black are brighter, solid pixels stay the same
+    let mut brighter = 0;
+    for (pa, pb) in a.buffer().iter().zip(b.buffer().iter()) {
+        assert!(pb.red() >= pa.red());
+        if pb.red() > pa.red() {
+            brighter += 1;
+        }
+    }
+    assert!(brighter > 0);
+}
@@ -0,0 +1,69 @@
+use draw::*;
+
+fn blend(mode: MixBlendMode, src: Rgba, dst: Rgba) -> Rgba {
+    let mut result = dst;
+    ImageBlendMode::blend_color(&mode, &mut result, src);
+    result
+}
+
+fn close(a: Rgba, b: Rgba) -> bool {
+    a.0.iter()
+        .zip(b.0.iter())
+        .all(|(a, b)| (*a as i32 - *b as i32).abs() <= 1)
+}
+
+#[test]
+fn separable_blend_test() {
+    let orange = Rgba([255, 128, 0, 255]);
+    let gray = Rgba([128, 128, 128, 255]);
+    let white = Rgba([255, 255, 255, 255]);
+    let black = Rgba([0, 0, 0, 255]);
+    let red = Rgba([255, 0, 0, 255]);
+
+    assert!(blend(MixBlendMode::Normal, gray, orange) == gray);
+    assert!(blend(MixBlendMode::Multiply, gray, orange) == Rgba([128, 64, 0, 255]));
+    assert!(blend(MixBlendMode::Screen, orange, black) == orange);
+    assert!(blend(MixBlendMode::Darken, gray, orange) == Rgba([128, 128, 0, 255]));
+    assert!(blend(MixBlendMode::Lighten, gray, orange) == Rgba([255, 128, 128, 255]));
+    assert!(blend(MixBlendMode::Difference, red, white) == Rgba([0, 255, 255, 255]));
+    assert!(blend(MixBlendMode::Exclusion, red, white) == Rgba([0, 255, 255, 255]));
+    assert!(blend(MixBlendMode::ColorDodge, white, gray) == white);
+    assert!(blend(MixBlendMode::ColorBurn, black, gray) == black);
+    assert!(close(blend(MixBlendMode::Overlay, gray, orange), orange));
+    assert!(close(blend(MixBlendMode::HardLight, orange, gray), orange));
+    assert!(close(blend(MixBlendMode::SoftLight, gray, orange), orange));
+
+    // The blended color only applies where the destination is opaque
+    let clear = Rgba([0, 0, 0, 0]);
+    assert!(blend(MixBlendMode::Multiply, orange, clear) == orange);
+    let half_gray = Rgba([128, 128, 128, 128]);
+    assert!(close(
+        blend(MixBlendMode::Multiply, half_gray, orange),
+        Rgba([191, 96, 0, 255])
+    ));
+}
+
+#[test]
+fn non_separable_blend_test() {
+    let red = Rgba([255, 0, 0, 255]);
+    let gray = Rgba([128, 128, 128, 255]);
+
+    // Red with the luminosity of the gray backdrop, clipped to the valid range
+    assert!(close(
+        blend(MixBlendMode::Color, red, gray),
+        Rgba([255, 73, 73, 255])
+    ));
+    assert!(close(blend(MixBlendMode::Hue, red, gray), gray));
+    assert!(close(blend(MixBlendMode::Saturation, red, gray), gray));
+    // Luminosity of a gray source on a gray backdrop keeps the gray
+    assert!(close(blend(MixBlendMode::Luminosity, gray, gray), gray));
+    let l = blend(MixBlendMode::Luminosity, gray, red);
+    let lum = 0.3 * l.red() as f32 + 0.59 * l.green() as f32 + 0.11 * l.blue() as f32;
+    assert!((lum - 128.0).abs() <= 2.0);
+
+    let mut img = Image::new_with_color([4, 4], red);
+    img.as_canvas()
+        .fill_rect_using(MixBlendMode::Color, [0, 0], [2, 2], Rgba([0, 0, 255, 255]));
+    assert!(img.get([1, 1]) != red);
+    assert!(img.get([2, 2]) == red);
+}
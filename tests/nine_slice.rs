@@ -0,0 +1,74 @@
+use draw::*;
+
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+const GREEN: Rgba = Rgba([0, 255, 0, 255]);
+const BLUE: Rgba = Rgba([0, 0, 255, 255]);
+
+fn frame_image() -> Image {
+    // 6x6 image with a 2 pixel red frame, a blue center and a green dot in the top edge
+    let mut img = Image::new_with_color([6, 6], RED);
+    img.as_canvas().fill_rect([2, 2], [2, 2], BLUE);
+    img.set([2, 0], GREEN);
+    img
+}
+
+#[test]
+fn nine_slice_test() {
+    let slice = NineSlice::new(frame_image(), [2, 2, 2, 2]).with_filter(SampleFilter::Nearest);
+    let mut img = Image::new([20, 10]);
+    img.as_canvas()
+        .draw_nine_slice(&slice, Rect::new([0, 0], [20, 10]));
+    for y in 0..10 {
+        for x in 0..20 {
+            let expected = if (2..18).contains(&x) && (2..8).contains(&y) {
+                BLUE
+            } else if y == 0 && (2..10).contains(&x) {
+                GREEN
+            } else {
+                RED
+            };
+            assert!(img.get([x, y]) == expected);
+        }
+    }
+
+    // Tiled edges repeat the dot at its original size
+    let slice = slice.with_edge_mode(SliceMode::Tile);
+    let mut img = Image::new([20, 10]);
+    img.as_canvas()
+        .draw_nine_slice(&slice, Rect::new([0, 0], [20, 10]));
+    for x in 2..18 {
+        let expected = if x % 2 == 0 { GREEN } else { RED };
+        assert!(img.get([x, 0]) == expected);
+    }
+
+    // The corners shrink if the rectangle is too small
+    let mut img = Image::new([2, 2]);
+    img.as_canvas()
+        .draw_nine_slice(&slice, Rect::new([0, 0], [2, 2]));
+    assert!(img.buffer().iter().all(|c| *c == RED));
+}
+
+#[test]
+fn nine_patch_test() {
+    let black = Rgba([0, 0, 0, 255]);
+    let mut patch = Image::new([8, 8]);
+    patch.as_canvas().draw_image(&frame_image(), [1, 1]);
+    for i in 3..5 {
+        patch.set([i, 0], black);
+        patch.set([0, i], black);
+    }
+    for i in 2..6 {
+        patch.set([i, 7], black);
+    }
+    let slice = NineSlice::from_nine_patch(&patch).unwrap();
+    assert!(slice.image().dim() == [6, 6]);
+    assert!(slice.insets() == [2, 2, 2, 2]);
+    assert!(slice.padding() == Some([2, 1, 2, 1]));
+    let content = slice.content_rect(Rect::new([10, 10], [20, 20]));
+    assert!(content == Rect::new([11, 12], [18, 16]));
+
+    patch.set([6, 0], black);
+    assert!(NineSlice::from_nine_patch(&patch).is_err());
+    patch.set([6, 0], Rgba([255, 0, 0, 255]));
+    assert!(NineSlice::from_nine_patch(&patch).is_err());
+}
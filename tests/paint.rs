@@ -0,0 +1,70 @@
+use draw::*;
+
+#[test]
+fn paint_pattern_test() {
+    let mut tile = Image::new([2, 2]);
+    tile.set([0, 0], Rgba([255, 0, 0, 255]));
+    tile.set([1, 0], Rgba([0, 255, 0, 255]));
+    tile.set([0, 1], Rgba([0, 0, 255, 255]));
+    tile.set([1, 1], Rgba([255, 255, 255, 255]));
+
+    let pattern = ImagePattern::new(&tile).with_offset([1, 0]);
+    let mut img = Image::new([6, 4]);
+    img.as_canvas().fill_rect([0, 0], [6, 4], pattern);
+    for y in 0..4 {
+        for x in 0..6 {
+            let expected = tile.get([((x + 1) % 2) as u32, (y % 2) as u32]);
+            assert!(img.get([x, y]) == expected);
+        }
+    }
+
+    // Without repeating, only the tile itself is drawn
+    let pattern = pattern.with_repeat(PatternRepeat::NoRepeat);
+    let mut img = Image::new([6, 4]);
+    img.as_canvas().fill(pattern);
+    assert!(img.get([1, 0]) == tile.get([0, 0]));
+    assert!(img.get([2, 1]) == tile.get([1, 1]));
+    assert!(img.get([0, 0]).alpha() == 0);
+    assert!(img.get([3, 0]).alpha() == 0);
+    assert!(img.get([1, 2]).alpha() == 0);
+}
+
+#[test]
+fn paint_shapes_test() {
+    // Closures compute the color from the pixel position
+    let stripes = |pos: [i32; 2]| {
+        if pos[0] % 2 == 0 {
+            Rgba([0, 0, 0, 255])
+        } else {
+            Rgba([255, 255, 255, 255])
+        }
+    };
+    let mut img = Image::new([20, 20]);
+    img.as_canvas().fill_circle([10.0, 10.0], 8.0, stripes);
+    assert!(img.get([10, 10]) == Rgba([0, 0, 0, 255]));
+    assert!(img.get([11, 10]) == Rgba([255, 255, 255, 255]));
+    assert!(img.get([0, 0]).alpha() == 0);
+
+    // Partially covered pixels use the color of the paint with a reduced alpha
+    let g = Gradient::linear([0.0, 0.0], [20.0, 0.0])
+        .with_stop(0.0, Rgba([255, 0, 0, 255]))
+        .with_stop(1.0, Rgba([0, 0, 255, 255]));
+    let mut img = Image::new([20, 20]);
+    img.as_canvas().fill_polygon(
+        &[[[2.0, 2.0], [18.0, 2.0], [18.0, 2.5], [2.0, 2.5]]],
+        FillRule::NonZero,
+        &g,
+    );
+    let c = img.get([5, 2]);
+    assert!(c.rgb() == g.color_at([5.5, 2.5]).rgb());
+    assert!(c.alpha() == 128);
+
+    // Gradients can be passed by value like colors
+    let mut copy = Image::new([20, 20]);
+    copy.as_canvas().fill_polygon(
+        &[[[2.0, 2.0], [18.0, 2.0], [18.0, 2.5], [2.0, 2.5]]],
+        FillRule::NonZero,
+        g.clone(),
+    );
+    assert!(copy == img);
+}
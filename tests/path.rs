@@ -0,0 +1,66 @@
+use draw::*;
+
+fn total_alpha(img: &Image) -> u32 {
+    img.buffer().iter().map(|c| c.alpha() as u32).sum()
+}
+
+#[test]
+fn path_fill_test() {
+    let c = Rgba([0, 0, 0, 255]);
+
+    // Closed by the fill even without calling close
+    let mut p = Path::new();
+    p.move_to([2.0, 2.0])
+        .line_to([6.0, 2.0])
+        .line_to([6.0, 6.0])
+        .line_to([2.0, 6.0]);
+    let mut img = Image::new([8, 8]);
+    img.as_canvas().fill_path(&p, FillRule::NonZero, c);
+    assert!(total_alpha(&img) == 16 * 255);
+
+    // Circle made of two arcs, the area should be close to pi * r^2
+    let mut p = Path::new();
+    p.move_to([4.0, 16.0])
+        .arc_to([12.0, 12.0], 0.0, false, true, [28.0, 16.0])
+        .arc_to([12.0, 12.0], 0.0, false, true, [4.0, 16.0])
+        .close();
+    let mut img = Image::new([32, 32]);
+    img.as_canvas().fill_path(&p, FillRule::NonZero, c);
+    let area = (total_alpha(&img) as f32) / 255.0;
+    let expected = std::f32::consts::PI * 144.0;
+    assert!((area - expected).abs() < 1.0);
+}
+
+#[test]
+fn path_curve_test() {
+    let c = Rgba([0, 0, 0, 255]);
+
+    // Quadratic and cubic curves equivalent to the same parabola
+    let mut quad = Path::new();
+    quad.move_to([0.0, 0.0])
+        .quad_to([16.0, 32.0], [32.0, 0.0])
+        .close();
+    let mut cubic = Path::new();
+    cubic
+        .move_to([0.0, 0.0])
+        .cubic_to(
+            [32.0 / 3.0, 64.0 / 3.0],
+            [64.0 / 3.0, 64.0 / 3.0],
+            [32.0, 0.0],
+        )
+        .close();
+
+    let mut img_quad = Image::new([32, 32]);
+    img_quad.as_canvas().fill_path(&quad, FillRule::NonZero, c);
+    let mut img_cubic = Image::new([32, 32]);
+    img_cubic
+        .as_canvas()
+        .fill_path(&cubic, FillRule::NonZero, c);
+
+    // Area of the parabola segment: 2/3 * width * height
+    let expected = 2.0 / 3.0 * 32.0 * 16.0;
+    for img in [img_quad, img_cubic].iter() {
+        let area = (total_alpha(img) as f32) / 255.0;
+        assert!((area - expected).abs() < 1.0);
+    }
+}
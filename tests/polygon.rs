@@ -0,0 +1,67 @@
+use draw::*;
+
+fn alpha_at(img: &Image, pos: [u32; 2]) -> u8 {
+    img.get(pos).alpha()
+}
+
+#[test]
+fn polygon_coverage_test() {
+    let c = Rgba([0, 0, 0, 255]);
+    let mut img = Image::new([8, 8]);
+    // Triangle covering exactly half of the pixels along the diagonal
+    let tri = [[0.0, 0.0], [8.0, 0.0], [8.0, 8.0]];
+    img.as_canvas().fill_polygon(&[tri], FillRule::NonZero, c);
+    for i in 0..8 {
+        assert!(alpha_at(&img, [i, i]) == 128);
+        if i > 0 {
+            assert!(alpha_at(&img, [i, i - 1]) == 255);
+            assert!(alpha_at(&img, [i - 1, i]) == 0);
+        }
+    }
+}
+
+#[test]
+fn polygon_fill_rule_test() {
+    let c = Rgba([0, 0, 0, 255]);
+    let outer = [[0.0, 0.0], [8.0, 0.0], [8.0, 8.0], [0.0, 8.0]];
+    let same_dir = [[2.0, 2.0], [6.0, 2.0], [6.0, 6.0], [2.0, 6.0]];
+    let reversed = [[2.0, 2.0], [2.0, 6.0], [6.0, 6.0], [6.0, 2.0]];
+
+    let mut img = Image::new([8, 8]);
+    img.as_canvas()
+        .fill_polygon(&[outer, same_dir], FillRule::EvenOdd, c);
+    assert!(alpha_at(&img, [1, 1]) == 255);
+    assert!(alpha_at(&img, [4, 4]) == 0);
+
+    let mut img = Image::new([8, 8]);
+    img.as_canvas()
+        .fill_polygon(&[outer, same_dir], FillRule::NonZero, c);
+    assert!(alpha_at(&img, [4, 4]) == 255);
+
+    let mut img = Image::new([8, 8]);
+    img.as_canvas()
+        .fill_polygon(&[outer, reversed], FillRule::NonZero, c);
+    assert!(alpha_at(&img, [1, 1]) == 255);
+    assert!(alpha_at(&img, [4, 4]) == 0);
+}
+
+#[test]
+fn polygon_clipping_test() {
+    let c = Rgba([0, 0, 0, 255]);
+    let mut img = Image::new([8, 8]);
+    // Mostly outside of the canvas, the visible part still has to be filled
+    let big = [
+        [-100.0, -100.0],
+        [100.0, -100.0],
+        [100.0, 4.0],
+        [-100.0, 4.0],
+    ];
+    if let Some(mut sc) = img.as_canvas().into_sub_canvas([2, 2], [4, 4]) {
+        sc.fill_polygon(&[big], FillRule::EvenOdd, c);
+    }
+    assert!(alpha_at(&img, [1, 1]) == 0);
+    assert!(alpha_at(&img, [2, 2]) == 255);
+    assert!(alpha_at(&img, [5, 3]) == 255);
+    assert!(alpha_at(&img, [5, 4]) == 0);
+    assert!(alpha_at(&img, [6, 3]) == 0);
+}
@@ -0,0 +1,60 @@
+use draw::*;
+
+const R: Rgba = Rgba([255, 0, 0, 255]);
+const B: Rgba = Rgba([0, 0, 255, 255]);
+const T: Rgba = Rgba([0, 0, 0, 0]);
+
+fn composite(op: PorterDuff, src: Rgba, dst: Rgba) -> Rgba {
+    let mut result = dst;
+    ImageBlendMode::blend_color(&op, &mut result, src);
+    result
+}
+
+#[test]
+fn porter_duff_test() {
+    // (operator, [src over dst, src over transparent, transparent over dst])
+    let cases = [
+        (PorterDuff::Src, [R, R, T]),
+        (PorterDuff::Dst, [B, T, B]),
+        (PorterDuff::SrcOver, [R, R, B]),
+        (PorterDuff::DstOver, [B, R, B]),
+        (PorterDuff::SrcIn, [R, T, T]),
+        (PorterDuff::DstIn, [B, T, T]),
+        (PorterDuff::SrcOut, [T, R, T]),
+        (PorterDuff::DstOut, [T, T, B]),
+        (PorterDuff::SrcAtop, [R, T, B]),
+        (PorterDuff::DstAtop, [B, R, T]),
+        (PorterDuff::Xor, [T, R, B]),
+        (PorterDuff::Plus, [Rgba([255, 0, 255, 255]), R, B]),
+    ];
+    for (op, expected) in cases.iter() {
+        assert!(composite(*op, R, B) == expected[0]);
+        assert!(composite(*op, R, T) == expected[1]);
+        assert!(composite(*op, T, B) == expected[2]);
+    }
+
+    let half_red = Rgba([255, 0, 0, 128]);
+    assert!(composite(PorterDuff::SrcIn, half_red, B) == half_red);
+    assert!(composite(PorterDuff::DstOut, half_red, B) == Rgba([0, 0, 255, 127]));
+}
+
+#[test]
+fn porter_duff_shape_test() {
+    // Punch a hole into an opaque image
+    let mut img = Image::new_with_color([20, 20], B);
+    img.as_canvas()
+        .fill_circle_using(PorterDuff::DstOut, [10.0, 10.0], 5.0, R);
+    assert!(img.get([10, 10]) == T);
+    assert!(img.get([1, 1]) == B);
+    let edge = img.get([13, 6]);
+    assert!(edge.alpha() > 0 && edge.alpha() < 255);
+
+    // Draw a sprite only where the destination is opaque
+    let mut img = Image::new([4, 4]);
+    img.as_canvas().fill_rect([0, 0], [2, 4], B);
+    let sprite = Image::new_with_color([4, 4], R);
+    img.as_canvas()
+        .draw_image_using(PorterDuff::SrcAtop, &sprite, [0, 0]);
+    assert!(img.get([1, 1]) == R);
+    assert!(img.get([2, 1]) == T);
+}
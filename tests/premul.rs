@@ -0,0 +1,45 @@
+use draw::*;
+
+#[test]
+fn premul_conversion_test() {
+    let c = Rgba([200, 100, 50, 128]);
+    let p = PremulRgba::from(c);
+    assert!(p == PremulRgba([100, 50, 25, 128]));
+    assert!(Rgba::from(p) == Rgba([199, 100, 50, 128]));
+    assert!(PremulRgba::from(Rgba([1, 2, 3, 0])).to_rgba() == Rgba([0, 0, 0, 0]));
+
+    let mut img = Image::new([3, 2]);
+    img.set([0, 0], Rgba([255, 0, 0, 255]));
+    img.set([1, 1], Rgba([0, 255, 0, 51]));
+    let buf = img.to_premul_buffer();
+    assert!(buf[0] == PremulRgba([255, 0, 0, 255]));
+    assert!(buf[4] == PremulRgba([0, 51, 0, 51]));
+    assert!(Image::from_premul_buffer([3, 2], &buf) == img);
+}
+
+#[test]
+fn premul_blend_test() {
+    // The integer blending matches the floating point reference within rounding
+    let values = [0u8, 1, 17, 64, 127, 128, 200, 254, 255];
+    let mut max_diff = 0;
+    for &src_alpha in values.iter() {
+        for &dst_alpha in values.iter() {
+            for &src_value in values.iter() {
+                for &dst_value in values.iter() {
+                    let src = Rgba([src_value, 255 - src_value, 0, src_alpha]);
+                    let dst = Rgba([dst_value, 0, 255 - dst_value, dst_alpha]);
+                    let (mut a, mut b) = (dst, dst);
+                    ImageBlendMode::blend_color(&ImageBlendTransparent, &mut a, src);
+                    ImageBlendMode::blend_color(&ImageBlendTransparentF32, &mut b, src);
+                    if a.alpha() == 0 && b.alpha() == 0 {
+                        continue;
+                    }
+                    for i in 0..4 {
+                        max_diff = max_diff.max((a.0[i] as i32 - b.0[i] as i32).abs());
+                    }
+                }
+            }
+        }
+    }
+    assert!(max_diff <= 1, "max_diff = {}", max_diff);
+}
@@ -0,0 +1,27 @@
+use draw::*;
+
+#[test]
+fn rows_iter_bottom_edge_test() {
+    let mut img = Image::new([3, 4]);
+    for y in 0..4 {
+        img.as_canvas().fill_rect([0, y], [3, 1], Rgba([y as u8, 0, 0, 255]));
+    }
+
+    // Every row of the image, without reading past the end of the buffer
+    assert!(img.rows().count() == 4);
+    let firsts: Vec<u8> = img.rows().map(|row| row[0].0[0]).collect();
+    assert!(firsts == vec![0, 1, 2, 3]);
+
+    // A region ends at its bottom edge, not one row below it
+    let rows: Vec<&[Rgba]> = img.rows_at([1u32, 1u32], [2, 2]).collect();
+    assert!(rows.len() == 2);
+    assert!(rows[0].len() == 2 && rows[1][0].0[0] == 2);
+    let positions: Vec<[i32; 2]> = img
+        .rows_at([0u32, 2u32], [3, 2])
+        .with_pos()
+        .map(|(pos, _)| pos)
+        .collect();
+    assert!(positions == vec![[0, 2], [0, 3]]);
+
+    assert!(img.rows_mut().count() == 4);
+}
@@ -0,0 +1,94 @@
+use draw::*;
+use std::f32::consts::PI;
+
+fn area(img: &Image) -> f32 {
+    let sum: u32 = img.buffer().iter().map(|c| c.alpha() as u32).sum();
+    (sum as f32) / 255.0
+}
+
+#[test]
+fn shapes_fill_test() {
+    let c = Rgba([0, 0, 0, 255]);
+
+    let mut img = Image::new([32, 32]);
+    img.as_canvas().fill_circle([16.0, 16.0], 10.0, c);
+    assert!((area(&img) - PI * 100.0).abs() < 1.0);
+
+    let mut img = Image::new([32, 32]);
+    img.as_canvas().fill_ellipse([16.0, 16.0], [12.0, 6.0], c);
+    assert!((area(&img) - PI * 72.0).abs() < 1.0);
+
+    // Quarter pie in the lower right quadrant (y-axis points down)
+    let mut img = Image::new([32, 32]);
+    img.as_canvas()
+        .fill_pie([16.0, 16.0], 12.0, 0.0, 0.5 * PI, c);
+    assert!((area(&img) - PI * 36.0).abs() < 1.0);
+    assert!(img.get([20, 20]).alpha() == 255);
+    assert!(img.get([12, 12]).alpha() == 0);
+
+    // A full donut segment leaves the center empty
+    let mut img = Image::new([32, 32]);
+    img.as_canvas()
+        .fill_donut_segment([16.0, 16.0], 6.0, 12.0, 0.0, 2.0 * PI, c);
+    assert!((area(&img) - PI * (144.0 - 36.0)).abs() < 1.5);
+    assert!(img.get([16, 16]).alpha() == 0);
+}
+
+#[test]
+fn shapes_outline_test() {
+    let c = Rgba([0, 0, 0, 255]);
+
+    // Ring of width 2 centered on the radius
+    let mut img = Image::new([32, 32]);
+    img.as_canvas().draw_circle([16.0, 16.0], 10.0, 2.0, c);
+    assert!((area(&img) - PI * (121.0 - 81.0)).abs() < 2.0);
+    assert!(img.get([16, 16]).alpha() == 0);
+
+    // Half of the ring, the butt caps add no area
+    let mut img = Image::new([32, 32]);
+    img.as_canvas()
+        .draw_arc([16.0, 16.0], 10.0, 0.0, PI, 2.0, c);
+    assert!((area(&img) - 0.5 * PI * (121.0 - 81.0)).abs() < 2.0);
+    assert!(img.get([16, 5]).alpha() == 0);
+}
+
+#[test]
+fn rounded_rect_test() {
+    let c = Rgba([0, 0, 0, 255]);
+    let r = Rect::new([4, 4], [24, 16]);
+
+    // Only the top left corner is rounded
+    let mut img = Image::new([32, 32]);
+    img.as_canvas()
+        .fill_rounded_rect(r, [8.0, 0.0, 0.0, 0.0], c);
+    assert!((area(&img) - (24.0 * 16.0 - (64.0 - 16.0 * PI))).abs() < 1.0);
+    assert!(img.get([4, 4]).alpha() == 0);
+    assert!(img.get([27, 4]).alpha() == 255);
+    assert!(img.get([27, 19]).alpha() == 255);
+
+    // Radii that do not fit are scaled down, resulting in a stadium
+    let mut img = Image::new([32, 32]);
+    img.as_canvas().fill_rounded_rect(r, [100.0; 4], c);
+    assert!((area(&img) - (8.0 * 16.0 + PI * 64.0)).abs() < 1.0);
+
+    // The outline stays within the rectangle
+    let mut img = Image::new([32, 32]);
+    img.as_canvas().draw_rounded_rect(r, [4.0; 4], 2.0, c);
+    assert!(img.get([16, 3]).alpha() == 0);
+    assert!(img.get([16, 4]).alpha() == 255);
+    assert!(img.get([16, 5]).alpha() == 255);
+    assert!(img.get([16, 6]).alpha() == 0);
+    assert!(img.get([3, 12]).alpha() == 0);
+    assert!(img.get([27, 12]).alpha() == 255);
+    assert!(img.get([28, 12]).alpha() == 0);
+
+    // Radii smaller than half the width keep the outer corners rounded
+    let mut img = Image::new([32, 32]);
+    let r = Rect::new([4, 4], [24, 24]);
+    img.as_canvas().draw_rounded_rect(r, [4.0; 4], 10.0, c);
+    assert!(img.get([4, 4]).alpha() == 0);
+    assert!(img.get([27, 27]).alpha() == 0);
+    assert!(img.get([8, 8]).alpha() == 255);
+    assert!(img.get([13, 13]).alpha() == 255);
+    assert!(img.get([16, 16]).alpha() == 0);
+}
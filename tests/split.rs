@@ -0,0 +1,82 @@
+use draw::*;
+
+const WHITE: Rgba = Rgba([255, 255, 255, 255]);
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+
+#[test]
+fn split_rows_test() {
+    let mut img = Image::new_with_color([20, 10], WHITE);
+    {
+        let mut c = img.as_canvas();
+        let mut sc = c.sub_canvas([2, 1], [16, 7]).unwrap();
+        let bands = sc.split_rows(3);
+        let rects: Vec<Rect> = bands.iter().map(|b| b.rect()).collect();
+        assert!(rects[0] == Rect::new([2, 1], [16, 2]));
+        assert!(rects[1] == Rect::new([2, 3], [16, 2]));
+        assert!(rects[2] == Rect::new([2, 5], [16, 3]));
+        std::thread::scope(|s| {
+            for (i, mut band) in bands.into_iter().enumerate() {
+                s.spawn(move || {
+                    let c = (i as u8) * 100;
+                    band.fill(Rgba([c, c, c, 255]));
+                    band.fill_circle([10.0, 10.0], 100.0, Rgba([c, 0, 0, 128]));
+                });
+            }
+        });
+        assert!(sc.split_rows(10).len() == 7);
+    }
+    let expected = |i: u8| {
+        let mut c = Rgba([i * 100, i * 100, i * 100, 255]);
+        ColorBlendMode::blend_color(&ColorBlendTransparent, &mut c, &Rgba([i * 100, 0, 0, 128]));
+        c
+    };
+    assert!(img.get([1, 1]) == WHITE);
+    assert!(img.get([2, 1]) == expected(0));
+    assert!(img.get([17, 4]) == expected(1));
+    assert!(img.get([10, 7]) == expected(2));
+    assert!(img.get([10, 8]) == WHITE);
+}
+
+#[test]
+fn split_at_test() {
+    let mut img = Image::new_with_color([10, 10], WHITE);
+    {
+        let mut c = img.as_canvas();
+        let (left, right) = c.split_at_x(4);
+        let (mut left, right) = (left.unwrap(), right.unwrap());
+        assert!(left.rect() == Rect::new([0, 0], [4, 10]));
+        assert!(right.rect() == Rect::new([4, 0], [6, 10]));
+
+        // Parts can not access the pixels of the other parts
+        let params = FloodFill::new(0.0, Connectivity::Four).with_within_canvas(false);
+        let mask = left.flood_fill_using(ColorBlendOverwrite, [0, 0], RED, &params);
+        assert!(mask.unwrap().rect() == Rect::new([0, 0], [4, 10]));
+
+        let (top, bottom) = c.split_at_y(-5);
+        assert!(top.is_none());
+        assert!(bottom.unwrap().rect() == Rect::new([0, 0], [10, 10]));
+    }
+    assert!(img.get([3, 9]) == RED);
+    assert!(img.get([4, 0]) == WHITE);
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn par_rows_test() {
+    use rayon::prelude::*;
+
+    let mut img = Image::new_with_color([8, 6], WHITE);
+    img.par_rows_mut().enumerate().for_each(|(y, row)| {
+        row[0] = Rgba([y as u8, 0, 0, 255]);
+    });
+    {
+        let mut c = img.as_canvas();
+        let mut sc = c.sub_canvas([2, 2], [3, 3]).unwrap();
+        assert!(sc.par_rows_mut().len() == 3);
+        sc.par_rows_mut().for_each(|row| row.fill(RED));
+    }
+    assert!(img.get([0, 5]) == Rgba([5, 0, 0, 255]));
+    assert!(img.get([2, 2]) == RED);
+    assert!(img.get([4, 4]) == RED);
+    assert!(img.get([5, 4]) == WHITE);
+}
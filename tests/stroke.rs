@@ -0,0 +1,113 @@
+use draw::*;
+
+#[test]
+fn stroke_caps_test() {
+    let c = Rgba([0, 0, 0, 255]);
+    let mut img = Image::new([16, 16]);
+    let style = StrokeStyle::new(4.0);
+    img.as_canvas()
+        .stroke_polyline(&[[4.0, 8.0], [12.0, 8.0]], &style, c);
+    for y in 0..16 {
+        for x in 0..16 {
+            let inside = (4..12).contains(&x) && (6..10).contains(&y);
+            assert!(img.get([x, y]).alpha() == if inside { 255 } else { 0 });
+        }
+    }
+
+    let mut img = Image::new([16, 16]);
+    let style = style.with_cap(LineCap::Square);
+    img.as_canvas()
+        .stroke_polyline(&[[4.0, 8.0], [12.0, 8.0]], &style, c);
+    assert!(img.get([2, 6]).alpha() == 255);
+    assert!(img.get([13, 9]).alpha() == 255);
+    assert!(img.get([1, 8]).alpha() == 0);
+    assert!(img.get([14, 8]).alpha() == 0);
+}
+
+#[test]
+fn stroke_joins_test() {
+    let c = Rgba([0, 0, 0, 255]);
+    let pts = [[2.0, 4.0], [10.0, 4.0], [10.0, 14.0]];
+
+    // Miter join fills the outer corner completely
+    let mut img = Image::new([16, 16]);
+    img.as_canvas()
+        .stroke_polyline(&pts, &StrokeStyle::new(4.0), c);
+    assert!(img.get([11, 2]).alpha() == 255);
+    assert!(img.get([9, 5]).alpha() == 255);
+
+    // Bevel join cuts the outer corner diagonally
+    let mut img = Image::new([16, 16]);
+    let style = StrokeStyle::new(4.0).with_join(LineJoin::Bevel);
+    img.as_canvas().stroke_polyline(&pts, &style, c);
+    assert!(img.get([11, 2]).alpha() == 0);
+    assert!(img.get([10, 3]).alpha() == 255);
+
+    // Miter limit falls back to a bevel
+    let mut img = Image::new([16, 16]);
+    let style = StrokeStyle::new(4.0).with_join(LineJoin::Miter(1.2));
+    img.as_canvas().stroke_polyline(&pts, &style, c);
+    assert!(img.get([11, 2]).alpha() == 0);
+}
+
+#[test]
+fn stroke_dash_test() {
+    let c = Rgba([0, 0, 0, 255]);
+
+    // Dashes of 3 pixels separated by gaps of 1 pixel, starting 1 pixel into the pattern
+    let style = StrokeStyle::new(1.0).with_dash(&[3.0, 1.0], 1.0);
+    let mut img = Image::new([12, 3]);
+    img.as_canvas()
+        .stroke_line([0.0, 1.5], [12.0, 1.5], &style, c);
+    let row: Vec<bool> = (0..12).map(|x| img.get([x, 1]).alpha() == 255).collect();
+    let expected: Vec<bool> = (0..12).map(|x| (x + 1) % 4 != 3).collect();
+    assert!(row == expected);
+    assert!(img.get([0, 0]).alpha() == 0);
+
+    // The pattern continues around the corner, the first dash ends 1.5 pixels after it
+    let style = StrokeStyle::new(1.0).with_dash(&[5.0, 3.0], 0.0);
+    let mut img = Image::new([8, 8]);
+    img.as_canvas()
+        .stroke_polyline(&[[0.0, 0.5], [3.5, 0.5], [3.5, 8.0]], &style, c);
+    assert!(img.get([2, 0]).alpha() == 255);
+    assert!(img.get([3, 1]).alpha() == 255);
+    assert!(img.get([3, 2]).alpha() == 0);
+
+    // The dash that crosses the starting corner of a closed outline is not interrupted
+    let style = StrokeStyle::new(2.0).with_dash(&[4.0, 4.0], 2.0);
+    let mut img = Image::new([16, 16]);
+    img.as_canvas()
+        .stroke_rect(Rect::new([0, 0], [16, 16]), &style, c);
+    assert!(img.get([0, 0]).alpha() == 255);
+    assert!(img.get([1, 1]).alpha() == 255);
+    assert!(img.get([0, 2]).alpha() == 255);
+    assert!(img.get([2, 0]).alpha() == 255);
+    assert!(img.get([0, 3]).alpha() == 0);
+    assert!(img.get([3, 0]).alpha() == 0);
+    assert!(img.get([7, 0]).alpha() == 255);
+    assert!(img.get([8, 8]).alpha() == 0);
+}
+
+#[test]
+fn stroke_overlap_coverage_test() {
+    let c = Rgba([0, 0, 0, 255]);
+
+    // The edges of the stroke cover half of a pixel, also where the round caps and joins meet
+    // the segments
+    let style = StrokeStyle::new(3.0)
+        .with_cap(LineCap::Round)
+        .with_join(LineJoin::Round);
+    let mut img = Image::new([32, 32]);
+    img.as_canvas()
+        .stroke_polyline(&[[6.0, 10.0], [24.0, 10.0], [24.0, 28.0]], &style, c);
+    let edge = img.get([15, 8]).alpha();
+    assert!((127..=128).contains(&edge));
+    for x in 0..22 {
+        assert!(img.get([x, 8]).alpha() <= edge);
+        assert!(img.get([x, 11]).alpha() <= edge);
+    }
+    for y in 12..32 {
+        assert!(img.get([22, y]).alpha() <= edge);
+        assert!(img.get([25, y]).alpha() <= edge);
+    }
+}
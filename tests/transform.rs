@@ -0,0 +1,169 @@
+use draw::*;
+
+const WHITE: Rgba = Rgba([255, 255, 255, 255]);
+const RED: Rgba = Rgba([255, 0, 0, 255]);
+const BLUE: Rgba = Rgba([0, 0, 255, 255]);
+
+fn draw_widget(c: &mut Canvas, icon: &Image) {
+    c.fill_rect([0, 0], [10, 6], RED);
+    c.draw_rect([0, 0], [10, 6], BLUE);
+    c.fill_circle([5.0, 9.0], 3.0, BLUE);
+    c.draw_line([0.5, 13.5], [9.5, 13.5], LineMode::AntiAliased, RED);
+    c.draw_image(icon, [2, 2]);
+    c.fill_rect([6, 2], [2, 2], |pos: [i32; 2]| {
+        Rgba([0, 255, (pos[0] * 10) as u8, 255])
+    });
+}
+
+#[test]
+fn transform_local_origin_test() {
+    let icon = Image::new_with_color([2, 2], Rgba([0, 255, 0, 128]));
+    let font_cache =
+        FontCache::ttf_from_static(include_bytes!("../examples/carlito/Carlito-Regular.ttf"))
+            .unwrap();
+
+    // Drawing with local coordinates in a sub-canvas matches drawing at the origin
+    let mut expected = Image::new_with_color([16, 20], WHITE);
+    draw_widget(&mut expected.as_canvas(), &icon);
+    expected
+        .as_canvas()
+        .draw_text(&mut font_cache.font(), 12, BLUE, "Ab", [0, 4], None);
+
+    let mut img = Image::new_with_color([30, 30], WHITE);
+    {
+        let mut c = img.as_canvas();
+        let mut sc = c.sub_canvas([7, 5], [16, 20]).unwrap().with_local_origin();
+        draw_widget(&mut sc, &icon);
+        sc.draw_text(&mut font_cache.font(), 12, BLUE, "Ab", [0, 4], None);
+    }
+    for y in 0..30 {
+        for x in 0..30 {
+            let c = expected.as_canvas().try_get_color([x - 7, y - 5]);
+            assert!(img.get([x, y]) == c.unwrap_or(WHITE));
+        }
+    }
+}
+
+#[test]
+fn transform_scale_test() {
+    let mut icon = Image::new_with_color([2, 2], RED);
+    icon.set([1, 1], BLUE);
+
+    let mut img = Image::new_with_color([20, 20], WHITE);
+    {
+        let mut c = img.as_canvas();
+        c.translate(2.0, 2.0);
+        c.scale(2.0, 2.0);
+        assert!(c.transform() == Affine([2.0, 0.0, 0.0, 2.0, 2.0, 2.0]));
+        c.fill_rect([0, 0], [2, 1], BLUE);
+        c.draw_image(&icon, [3, 3]);
+        assert!(c.try_set_color([1, 4], RED));
+    }
+    assert!(img.get([1, 1]) == WHITE);
+    assert!(img.get([2, 2]) == BLUE);
+    assert!(img.get([5, 3]) == BLUE);
+    assert!(img.get([6, 4]) == WHITE);
+    assert!(img.get([8, 8]) == RED);
+    assert!(img.get([9, 9]) == RED);
+    assert!(img.get([10, 10]) == BLUE);
+    assert!(img.get([11, 11]) == BLUE);
+    assert!(img.get([12, 12]) == WHITE);
+    assert!(img.get([5, 11]) == RED);
+    assert!(img.get([4, 10]) == WHITE);
+
+    // Single pixel accessors use the same local coordinates
+    let mut img = Image::new_with_color([20, 20], WHITE);
+    {
+        let mut c = img.as_canvas();
+        let mut sc = c.sub_canvas([5, 5], [5, 5]).unwrap().with_local_origin();
+        assert!(sc.try_set_color([0, 0], RED));
+        assert!(sc.try_get_color([0, 0]) == Some(RED));
+        *sc.try_get_color_mut([4, 4]).unwrap() = BLUE;
+        assert!(sc.try_get_color([4, 4]) == Some(BLUE));
+        assert!(sc.try_get_color([5, 0]).is_none());
+
+        let mut c = c.with_transform(Affine::scale(2.0, 2.0));
+        assert!(c.try_set_color([7, 1], BLUE));
+        assert!(c.try_get_color([7, 1]) == Some(BLUE));
+    }
+    assert!(img.get([5, 5]) == RED);
+    assert!(img.get([9, 9]) == BLUE);
+    assert!(img.get([15, 3]) == BLUE);
+
+    // Scaled text is rendered offscreen and resampled
+    let font_cache =
+        FontCache::ttf_from_static(include_bytes!("../examples/carlito/Carlito-Regular.ttf"))
+            .unwrap();
+    let text_width = |scale: f32| {
+        let mut img = Image::new_with_color([80, 40], WHITE);
+        let mut c = img.as_canvas();
+        c.scale(scale, scale);
+        c.draw_text(&mut font_cache.font(), 12, BLUE, "Ab", [0, 0], None);
+        (0..80)
+            .filter(|x| (0..40).any(|y| img.get([*x, y]) != WHITE))
+            .count()
+    };
+    let (w1, w2) = (text_width(1.0), text_width(2.0));
+    assert!(w1 > 0 && (2 * w1 - 2..=2 * w1 + 2).contains(&w2));
+
+    // Zooming out only allocates an offscreen buffer for the text, not for the whole canvas
+    let mut img = Image::new_with_color([2000, 2000], WHITE);
+    {
+        let mut c = img.as_canvas();
+        c.scale(0.05, 0.05);
+        c.draw_text(&mut font_cache.font(), 200, BLUE, "Ab", [100, 100], None);
+        c.draw_text(&mut font_cache.font(), 200, BLUE, "Ab", [-5000, 0], None);
+    }
+    let drawn: Vec<[u32; 2]> = (0..40)
+        .flat_map(|y| (0..40).map(move |x| [x, y]))
+        .filter(|pos| img.get(*pos) != WHITE)
+        .collect();
+    assert!(!drawn.is_empty());
+    assert!(drawn.iter().all(|[x, y]| *x >= 5 && *y >= 5 && *x < 30 && *y < 30));
+
+    // Scaled text uses the blend mode, like text drawn without a transformation
+    let mut img = Image::new_with_color([80, 40], WHITE);
+    {
+        let mut c = img.as_canvas();
+        c.scale(2.0, 2.0);
+        let color = BLUE.with_alpha(128);
+        c.draw_text_using(
+            ColorBlendOverwrite,
+            &mut font_cache.font(),
+            12,
+            color,
+            "Ab",
+            [0, 0],
+            None,
+        );
+    }
+    assert!(img.buffer().iter().any(|p| p.alpha() < 255));
+    assert!(img.buffer().iter().all(|p| *p == WHITE || p.alpha() <= 128));
+
+    // Gradients are evaluated at the transformed pixel centers, not at whole local pixels
+    let g = Gradient::linear([0.0, 0.0], [10.0, 0.0])
+        .with_stop(0.0, Rgba([0, 0, 0, 255]))
+        .with_stop(1.0, WHITE);
+    let mut img = Image::new([40, 1]);
+    {
+        let mut c = img.as_canvas();
+        c.scale(4.0, 4.0);
+        c.fill_rect([0, 0], [10, 1], &g);
+    }
+    for x in 1..40 {
+        assert!(img.get([x, 0]).0[0] > img.get([x - 1, 0]).0[0]);
+    }
+
+    // Rotated rectangles are anti-aliased
+    let mut img = Image::new_with_color([20, 20], WHITE);
+    img.as_canvas()
+        .with_transform(Affine::rotate_around(0.3, [10.0, 10.0]))
+        .fill_rect([5, 5], [10, 10], RED);
+    assert!(img.get([10, 10]) == RED);
+    assert!(img.get([1, 1]) == WHITE);
+    let edge = (0..20).any(|x| {
+        let c = img.get([x, 5]);
+        c != RED && c != WHITE
+    });
+    assert!(edge);
+}